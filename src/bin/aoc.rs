@@ -0,0 +1,125 @@
+use aoc23::registry::{solver, Solver};
+use std::env;
+use std::fs;
+use std::process::exit;
+
+const USAGE: &str = "Usage: aoc run <day> [--part <1|2>] [--input <path>]
+       aoc report <day> [--part <1|2>] [--format <format>] [--input <path>]";
+
+#[derive(Debug)]
+struct RunArgs {
+    day: u8,
+    part: Option<u8>,
+    input: Option<String>,
+    format: Option<String>,
+}
+
+impl RunArgs {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut args = args.iter();
+        let day = args
+            .next()
+            .ok_or("Missing day")?
+            .parse::<u8>()
+            .map_err(|_| "Day must be a number")?;
+        let mut part = None;
+        let mut input = None;
+        let mut format = None;
+
+        while let Some(arg) = args.next() {
+            let value = args.next().ok_or(format!("Missing value for {}", arg))?;
+
+            match arg.as_str() {
+                "--part" => part = Some(value.parse::<u8>().map_err(|_| "Part must be a number")?),
+                "--input" => input = Some(value.to_string()),
+                "--format" => format = Some(value.to_string()),
+                _ => return Err(format!("Unknown option {}", arg)),
+            }
+        }
+
+        Ok(Self {
+            day,
+            part,
+            input,
+            format,
+        })
+    }
+
+    fn solver(&self) -> Result<&'static Solver, String> {
+        solver(self.day).ok_or(format!("Day {} is not solved", self.day))
+    }
+
+    fn contents(&self, solver: &Solver) -> Result<String, String> {
+        let path = self.input.clone().unwrap_or(solver.default_input());
+
+        fs::read_to_string(&path).map_err(|err| format!("Failed to read {}: {}", path, err))
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let solver = args.solver()?;
+    let contents = args.contents(solver)?;
+
+    if args.format.is_some() {
+        return Err("--format only applies to reports".to_string());
+    }
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    for part in parts {
+        let part_fn = solver
+            .part(part)
+            .ok_or(format!("Day {} has no part {}", args.day, part))?;
+        let answer = part_fn(&contents).map_err(|err| err.to_string())?;
+
+        println!("Part {}: {}", part, answer);
+    }
+
+    Ok(())
+}
+
+fn report(args: RunArgs) -> Result<(), String> {
+    let solver = args.solver()?;
+    let report_fn = solver
+        .report
+        .ok_or(format!("Day {} has no report", args.day))?;
+    let part = args.part.unwrap_or(1);
+    let format = args.format.as_deref().unwrap_or(solver.formats[0]);
+
+    if solver.part(part).is_none() {
+        return Err(format!("Day {} has no part {}", args.day, part));
+    }
+    if !solver.formats.contains(&format) {
+        return Err(format!(
+            "Day {} reports in {}, not {}",
+            args.day,
+            solver.formats.join(" or "),
+            format
+        ));
+    }
+
+    let contents = args.contents(solver)?;
+    let report = report_fn(&contents, part, format).map_err(|err| err.to_string())?;
+
+    println!("{}", report);
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(|arg| arg.as_str()) {
+        Some("run") => RunArgs::parse(&args[1..]).and_then(run),
+        Some("report") => RunArgs::parse(&args[1..]).and_then(report),
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        exit(1);
+    }
+}
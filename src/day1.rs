@@ -0,0 +1,399 @@
+use crate::error::ParseError;
+use crate::registry::Solver;
+use crate::solution::{Report, Solution};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+// Tokens that read as a digit, such as numerals or spelled out number words.
+// They are kept in a trie, so a single walk from an offset finds the longest token starting there.
+#[derive(Debug, Clone)]
+pub struct Vocabulary {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: HashMap<char, usize>,
+    // Digit of the token ending at this node, if any
+    digit: Option<u32>,
+}
+
+impl Vocabulary {
+    // A token listed twice reads as the digit it was listed with last
+    pub fn new(tokens: &[(&str, u32)]) -> Self {
+        let mut vocabulary = Self {
+            nodes: vec![TrieNode::default()],
+        };
+        tokens
+            .iter()
+            .for_each(|&(token, digit)| vocabulary.insert(token, digit));
+
+        vocabulary
+    }
+
+    pub fn numerals() -> Self {
+        let mut vocabulary = Self::new(&[]);
+        (0..10).for_each(|digit| vocabulary.insert(&digit.to_string(), digit));
+
+        vocabulary
+    }
+
+    pub fn english() -> Self {
+        Self::numerals().with_words(&[
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ])
+    }
+
+    pub fn dutch() -> Self {
+        Self::numerals().with_words(&[
+            "een", "twee", "drie", "vier", "vijf", "zes", "zeven", "acht", "negen",
+        ])
+    }
+
+    pub fn german() -> Self {
+        Self::numerals().with_words(&[
+            "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+        ])
+    }
+
+    // Adds the words for the digits 1 up to 9, in that order
+    pub fn with_words(mut self, words: &[&str]) -> Self {
+        words
+            .iter()
+            .zip(1..)
+            .for_each(|(word, digit)| self.insert(word, digit));
+        self
+    }
+
+    fn insert(&mut self, token: &str, digit: u32) {
+        let node = token.chars().fold(0, |node, char| {
+            if let Some(&child) = self.nodes[node].children.get(&char) {
+                return child;
+            }
+
+            self.nodes.push(TrieNode::default());
+            let child = self.nodes.len() - 1;
+            self.nodes[node].children.insert(char, child);
+            child
+        });
+
+        // The empty token would match everywhere, so it never reads as a digit
+        if node != 0 {
+            self.nodes[node].digit = Some(digit);
+        }
+    }
+
+    // Digit of the longest token starting at a byte offset of the line, if any
+    fn digit_at(&self, line: &str, offset: usize) -> Option<u32> {
+        let mut node = 0;
+        let mut digit = None;
+
+        for char in line[offset..].chars() {
+            match self.nodes[node].children.get(&char) {
+                Some(&child) => node = child,
+                None => break,
+            }
+
+            digit = self.nodes[node].digit.or(digit);
+        }
+
+        digit
+    }
+
+    // All (byte offset, digit) tokens of the line from left to right, including overlapping ones
+    pub fn digits<'a>(&'a self, line: &'a str) -> impl Iterator<Item = (usize, u32)> + 'a {
+        line.char_indices()
+            .filter_map(|(offset, _)| Some((offset, self.digit_at(line, offset)?)))
+    }
+
+    pub fn first_digit(&self, line: &str) -> Option<(usize, u32)> {
+        self.digits(line).next()
+    }
+
+    // Scans from the end of the line, so a token overlapping an earlier one still counts
+    pub fn last_digit(&self, line: &str) -> Option<(usize, u32)> {
+        line.char_indices()
+            .rev()
+            .find_map(|(offset, _)| Some((offset, self.digit_at(line, offset)?)))
+    }
+
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        Some(self.first_digit(line)?.1 * 10 + self.last_digit(line)?.1)
+    }
+}
+
+// Digits found on one line of the document, with their byte offsets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineReport {
+    pub number: usize,
+    pub digits: Vec<(usize, u32)>,
+}
+
+impl LineReport {
+    // None for a line without any digits
+    pub fn value(&self) -> Option<u32> {
+        Some(self.digits.first()?.1 * 10 + self.digits.last()?.1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalibrationReport {
+    pub lines: Vec<LineReport>,
+}
+
+impl CalibrationReport {
+    pub fn new(lines: &[String], vocabulary: &Vocabulary) -> Self {
+        Self {
+            lines: lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| LineReport {
+                    number: i + 1,
+                    digits: vocabulary.digits(line).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    // Lines without any digits count as 0
+    pub fn sum(&self) -> u32 {
+        self.lines.iter().filter_map(|line| line.value()).sum()
+    }
+
+    pub fn digitless(&self) -> Vec<usize> {
+        self.lines
+            .iter()
+            .filter(|line| line.value().is_none())
+            .map(|line| line.number)
+            .collect()
+    }
+
+    pub fn table(&self) -> String {
+        let rows: Vec<(String, String, String)> = self
+            .lines
+            .iter()
+            .map(|line| {
+                (
+                    line.number.to_string(),
+                    line.digits
+                        .iter()
+                        .map(|(offset, digit)| format!("{}@{}", digit, offset))
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                    line.value()
+                        .map_or("no digits".to_string(), |value| value.to_string()),
+                )
+            })
+            .collect();
+        let width = rows
+            .iter()
+            .map(|(_, digits, _)| digits.len())
+            .max()
+            .unwrap_or(0)
+            .max("digit@offset".len());
+
+        let mut table = format!("{:>5}  {:<width$}  value\n", "line", "digit@offset");
+        rows.iter().for_each(|(number, digits, value)| {
+            table += &format!("{:>5}  {:<width$}  {}\n", number, digits, value);
+        });
+        table += &format!("{:>5}  {:<width$}  {}", "", "sum", self.sum());
+
+        if !self.digitless().is_empty() {
+            table += &format!("\nlines without digits: {:?}", self.digitless());
+        }
+
+        table
+    }
+
+    pub fn json(&self) -> String {
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "{{\"line\":{},\"digits\":[{}],\"value\":{}}}",
+                    line.number,
+                    line.digits
+                        .iter()
+                        .map(|(offset, digit)| format!(
+                            "{{\"offset\":{},\"digit\":{}}}",
+                            offset, digit
+                        ))
+                        .collect::<Vec<String>>()
+                        .join(","),
+                    line.value()
+                        .map_or("null".to_string(), |value| value.to_string())
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"lines\":[{}],\"sum\":{},\"digitless\":{:?}}}",
+            lines.join(","),
+            self.sum(),
+            self.digitless()
+        )
+    }
+}
+
+pub fn calibration_sum(lines: &[String], vocabulary: &Vocabulary) -> u32 {
+    CalibrationReport::new(lines, vocabulary).sum()
+}
+
+pub fn pt1_calibration_sum(lines: &[String]) -> u32 {
+    calibration_sum(lines, &Vocabulary::numerals())
+}
+
+pub fn pt2_calibration_sum(lines: &[String]) -> u32 {
+    calibration_sum(lines, &Vocabulary::english())
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+
+    fn parse(contents: &str) -> Result<Vec<String>, ParseError> {
+        Ok(contents.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part1(lines: &Vec<String>) -> impl Display {
+        pt1_calibration_sum(lines)
+    }
+
+    fn part2(lines: &Vec<String>) -> impl Display {
+        pt2_calibration_sum(lines)
+    }
+}
+
+impl Report for Day1 {
+    const FORMATS: &'static [&'static str] = &["table", "json"];
+
+    fn report(lines: &Vec<String>, part: u8, format: &str) -> Result<String, ParseError> {
+        let vocabulary = match part {
+            1 => Vocabulary::numerals(),
+            _ => Vocabulary::english(),
+        };
+        let report = CalibrationReport::new(lines, &vocabulary);
+
+        Ok(match format {
+            "json" => report.json(),
+            _ => report.table(),
+        })
+    }
+}
+
+pub const SOLVER: Solver = Solver::reporting::<Day1>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(contents: &str) -> Vec<String> {
+        contents.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_part1() {
+        let lines = lines("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet");
+
+        assert_eq!(
+            Vocabulary::numerals().calibration_value("treb7uchet"),
+            Some(77)
+        );
+        assert_eq!(pt1_calibration_sum(&lines), 142);
+    }
+
+    #[test]
+    fn test_part2() {
+        let lines = lines(
+            "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen",
+        );
+
+        assert_eq!(pt2_calibration_sum(&lines), 281);
+    }
+
+    #[test]
+    fn test_report() {
+        let lines = lines("1abc2\nnone\ntreb7uchet");
+        let report = CalibrationReport::new(&lines, &Vocabulary::english());
+
+        assert_eq!(report.lines[0].digits, vec![(0, 1), (4, 2)]);
+        assert_eq!(report.lines[1].digits, vec![(1, 1)]);
+        assert_eq!(report.lines[2].value(), Some(77));
+        assert_eq!(report.sum(), 12 + 11 + 77);
+
+        let report = CalibrationReport::new(&lines, &Vocabulary::numerals());
+
+        assert_eq!(report.lines[1].value(), None);
+        assert_eq!(report.digitless(), vec![2]);
+        assert_eq!(report.sum(), 12 + 77);
+        assert_eq!(pt1_calibration_sum(&lines), 89);
+        assert_eq!(
+            report.table(),
+            [
+                " line  digit@offset  value",
+                "    1  1@0 2@4       12",
+                "    2                no digits",
+                "    3  7@4           77",
+                "       sum           89",
+                "lines without digits: [2]",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            report.json(),
+            concat!(
+                r#"{"lines":[{"line":1,"digits":[{"offset":0,"digit":1},{"offset":4,"digit":2}],"value":12},"#,
+                r#"{"line":2,"digits":[],"value":null},"#,
+                r#"{"line":3,"digits":[{"offset":4,"digit":7}],"value":77}],"sum":89,"digitless":[2]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let english = Vocabulary::english();
+
+        assert_eq!(english.calibration_value("oneight"), Some(18));
+        assert_eq!(english.calibration_value("twone"), Some(21));
+        assert_eq!(english.calibration_value("sevenineight"), Some(78));
+        assert_eq!(english.calibration_value("xthreeightwox"), Some(32));
+        assert_eq!(
+            english.digits("eightwone").collect::<Vec<_>>(),
+            vec![(0, 8), (4, 2), (6, 1)]
+        );
+        assert_eq!(english.last_digit("2oneight"), Some((3, 8)));
+        assert_eq!(english.first_digit("abc"), None);
+    }
+
+    #[test]
+    fn test_vocabularies() {
+        assert_eq!(Vocabulary::dutch().calibration_value("zevenegen"), Some(79));
+        assert_eq!(
+            Vocabulary::german().calibration_value("fünfzweins"),
+            Some(51)
+        );
+        assert_eq!(Vocabulary::german().last_digit("fünfzweins"), Some((7, 1)));
+        assert_eq!(
+            Vocabulary::new(&[("I", 1), ("V", 5)]).calibration_value("aVbIc"),
+            Some(51)
+        );
+        assert_eq!(Vocabulary::numerals().calibration_value("one2"), Some(22));
+
+        // The longest token wins, whatever order the tokens are listed in
+        let numerals = Vocabulary::new(&[("I", 1), ("IV", 4), ("V", 5), ("VIII", 8)]);
+        let reversed = Vocabulary::new(&[("VIII", 8), ("V", 5), ("IV", 4), ("I", 1)]);
+
+        for vocabulary in [numerals, reversed] {
+            assert_eq!(
+                vocabulary.digits("xIVIIIx").collect::<Vec<_>>(),
+                vec![(1, 4), (2, 8), (3, 1), (4, 1), (5, 1)]
+            );
+            assert_eq!(vocabulary.calibration_value("xIVx"), Some(45));
+            assert_eq!(vocabulary.calibration_value("VII"), Some(51));
+        }
+    }
+}
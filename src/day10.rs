@@ -1,12 +1,15 @@
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::registry::Solver;
+use crate::solution::Solution;
 use std::collections::HashMap;
-use std::fmt::Debug;
-use std::fs;
+use std::fmt::{Debug, Display};
 
-type Loc = (usize, usize);
+type Loc = Pos;
 type Vect = (i8, i8);
 
 #[derive(Debug, Eq, PartialEq)]
-struct Tile {
+pub struct Tile {
     x: usize,
     y: usize,
     kind: char,
@@ -57,27 +60,27 @@ impl Tile {
 type StepsMap = HashMap<Loc, usize>;
 
 #[derive(Debug)]
-struct Map {
-    tiles: HashMap<Loc, Tile>,
-    width: usize,
-    _height: usize,
+pub struct Map {
+    tiles: Grid<Tile>,
 }
 
 impl Map {
-    pub fn load(contents: String) -> Self {
-        Self {
-            tiles: contents
-                .lines()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .map(move |(x, char)| ((x, y), Tile { x, y, kind: char }))
-                })
-                .collect(),
-            width: contents.find("\n").unwrap(),
-            _height: contents.lines().count(),
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        // Any character other than a pipe counts as ground (the samples mark tiles with I and O)
+        let grid = Grid::parse(Day10::DAY, &contents, Some, "tile")?;
+
+        if !grid.iter().any(|(_, &kind)| kind == 'S') {
+            return Err(ParseError::new(
+                Day10::DAY,
+                grid.height(),
+                grid.width() + 1,
+                "start tile 'S'",
+            ));
         }
+
+        Ok(Self {
+            tiles: grid.map(|(x, y), &kind| Tile { x, y, kind }),
+        })
     }
 
     pub fn start_tile(&self) -> &Tile {
@@ -88,17 +91,11 @@ impl Map {
             .1
     }
 
-    pub fn get_tiles(&self, pos: Vec<Loc>) -> Vec<&Tile> {
-        pos.iter().filter_map(|pos| self.tiles.get(pos)).collect()
-    }
-
     pub fn get_adj_tiles(&self, tile: &Tile) -> Vec<&Tile> {
-        self.get_tiles(vec![
-            (tile.x, tile.y.saturating_sub(1)),
-            (tile.x, tile.y + 1),
-            (tile.x.saturating_sub(1), tile.y),
-            (tile.x + 1, tile.y),
-        ])
+        self.tiles
+            .neighbours_4(tile.loc())
+            .filter_map(|pos| self.tiles.get(pos))
+            .collect()
     }
 
     pub fn connecting_pipes(&self, tile: &Tile) -> Vec<&Tile> {
@@ -184,9 +181,9 @@ impl Map {
     }
 
     pub fn find_right_boundary(&self, origin: &Tile, bounds: &HashMap<Loc, Vect>) -> Option<&Tile> {
-        (origin.x..self.width)
+        (origin.x..self.tiles.width())
             .find(|&x| bounds.contains_key(&(x, origin.y)))
-            .and_then(|x| self.tiles.get(&(x, origin.y)))
+            .and_then(|x| self.tiles.get((x, origin.y)))
     }
 
     pub fn is_clockwise(&self, direction_map: &HashMap<Loc, Vect>) -> bool {
@@ -203,7 +200,8 @@ impl Map {
         let clockwise = self.is_clockwise(&direction_map);
 
         self.tiles
-            .values()
+            .iter()
+            .map(|(_, tile)| tile)
             .filter(|tile| !direction_map.contains_key(&tile.loc()))
             .for_each(|tile| {
                 if enclosed_map.contains_key(&tile.loc()) {
@@ -232,7 +230,7 @@ impl Map {
     }
 
     pub fn furthest_tile(&self) -> usize {
-        self.steps_map().values().max().unwrap().clone()
+        *self.steps_map().values().max().unwrap()
     }
 
     pub fn enclosed_tiles(&self) -> usize {
@@ -243,46 +241,53 @@ impl Map {
     }
 }
 
-fn main() {
-    // 7030
-    println!(
-        "Part 1: {}",
-        Map::load(fs::read_to_string("inputs/day10.txt").unwrap()).furthest_tile()
-    );
-    // 285
-    println!(
-        "Part 2: {}",
-        Map::load(fs::read_to_string("inputs/day10.txt").unwrap()).enclosed_tiles()
-    );
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Map;
+
+    fn parse(contents: &str) -> Result<Map, ParseError> {
+        Map::load(contents.to_string())
+    }
+
+    fn part1(map: &Map) -> impl Display {
+        map.furthest_tile()
+    }
+
+    fn part2(map: &Map) -> impl Display {
+        map.enclosed_tiles()
+    }
 }
 
+pub const SOLVER: Solver = Solver::of::<Day10>();
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_tile() {
-        assert_eq!(
-            Tile {
-                kind: '7',
-                x: 91,
-                y: 21
-            }
-            .connects_to(&Tile {
-                kind: '-',
-                x: 90,
-                y: 20
-            }),
-            true
-        );
+        assert!(Tile {
+            kind: '7',
+            x: 91,
+            y: 21
+        }
+        .connects_to(&Tile {
+            kind: '-',
+            x: 90,
+            y: 20
+        }));
     }
 
     #[test]
     fn test_directions_map() {
-        let map = Map::load(fs::read_to_string("samples/day10c.txt").unwrap());
+        let map = Map::load(fs::read_to_string("samples/day10c.txt").unwrap()).unwrap();
         let directions_map = map.direction_map();
 
-        assert_eq!(map.is_clockwise(&directions_map), false);
+        assert!(!map.is_clockwise(&directions_map));
 
         // .|L-7OF-J|.
         // .|II|O|II|.
@@ -300,10 +305,10 @@ mod tests {
         assert_eq!(tile_6_7.0, 0);
         assert_eq!(tile_6_7.1, 1); // downwards
 
-        let map = Map::load(fs::read_to_string("samples/day10e.txt").unwrap());
+        let map = Map::load(fs::read_to_string("samples/day10e.txt").unwrap()).unwrap();
         let directions_map = map.direction_map();
 
-        assert_eq!(map.is_clockwise(&directions_map), true);
+        assert!(map.is_clockwise(&directions_map));
 
         // L7
         // IL
@@ -323,11 +328,15 @@ mod tests {
     #[test]
     fn test_part1() {
         assert_eq!(
-            Map::load(fs::read_to_string("samples/day10a.txt").unwrap()).furthest_tile(),
+            Map::load(fs::read_to_string("samples/day10a.txt").unwrap())
+                .unwrap()
+                .furthest_tile(),
             4
         );
         assert_eq!(
-            Map::load(fs::read_to_string("samples/day10b.txt").unwrap()).furthest_tile(),
+            Map::load(fs::read_to_string("samples/day10b.txt").unwrap())
+                .unwrap()
+                .furthest_tile(),
             8
         );
     }
@@ -335,20 +344,40 @@ mod tests {
     #[test]
     fn test_part2() {
         assert_eq!(
-            Map::load(fs::read_to_string("samples/day10c.txt").unwrap()).enclosed_tiles(),
+            Map::load(fs::read_to_string("samples/day10c.txt").unwrap())
+                .unwrap()
+                .enclosed_tiles(),
             4
         );
         assert_eq!(
-            Map::load(fs::read_to_string("samples/day10d.txt").unwrap()).enclosed_tiles(),
+            Map::load(fs::read_to_string("samples/day10d.txt").unwrap())
+                .unwrap()
+                .enclosed_tiles(),
             4
         );
         assert_eq!(
-            Map::load(fs::read_to_string("samples/day10e.txt").unwrap()).enclosed_tiles(),
+            Map::load(fs::read_to_string("samples/day10e.txt").unwrap())
+                .unwrap()
+                .enclosed_tiles(),
             8
         );
         assert_eq!(
-            Map::load(fs::read_to_string("samples/day10f.txt").unwrap()).enclosed_tiles(),
+            Map::load(fs::read_to_string("samples/day10f.txt").unwrap())
+                .unwrap()
+                .enclosed_tiles(),
             10
         );
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Map::load(".....\n.S-7.\n.|.|.\n.L-J\n.....".to_string()).unwrap_err(),
+            ParseError::new(10, 4, 5, "row of 5 tiles")
+        );
+        assert_eq!(
+            Map::load(".....\n.F-7.\n.|.|.\n.L-J.\n.....".to_string()).unwrap_err(),
+            ParseError::new(10, 5, 6, "start tile 'S'")
+        );
+    }
 }
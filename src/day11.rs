@@ -1,9 +1,12 @@
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::registry::Solver;
+use crate::solution::Solution;
 use std::cell::Cell;
-use std::fmt::Debug;
-use std::fs;
+use std::fmt::{Debug, Display};
 
-#[derive(Debug)]
-struct Galaxy {
+#[derive(Debug, Clone)]
+pub struct Galaxy {
     number: u32,
     x: Cell<usize>,
     y: Cell<usize>,
@@ -15,43 +18,34 @@ impl Galaxy {
     }
 }
 
-#[derive(Debug)]
-struct Universe {
+#[derive(Debug, Clone)]
+pub struct Universe {
+    grid: Grid<char>,
     galaxies: Vec<Galaxy>,
-    width: usize,
-    height: usize,
 }
 
 impl Universe {
-    pub fn load(contents: String) -> Self {
-        let mut galaxy_count: u32 = 0;
-
-        Self {
-            galaxies: contents
-                .lines()
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        let grid = Grid::parse(
+            Day11::DAY,
+            &contents,
+            |char| ".#".contains(char).then_some(char),
+            "'.' or '#'",
+        )?;
+
+        Ok(Self {
+            galaxies: grid
+                .iter()
+                .filter(|(_, &char)| char == '#')
                 .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .filter_map(|(x, char)| {
-                            if char != '#' {
-                                return None;
-                            }
-
-                            galaxy_count += 1;
-
-                            Some(Galaxy {
-                                number: galaxy_count,
-                                x: Cell::new(x),
-                                y: Cell::new(y),
-                            })
-                        })
-                        .collect::<Vec<Galaxy>>()
+                .map(|(i, ((x, y), _))| Galaxy {
+                    number: i as u32 + 1,
+                    x: Cell::new(x),
+                    y: Cell::new(y),
                 })
                 .collect(),
-            width: contents.find("\n").unwrap(),
-            height: contents.lines().count() - 1,
-        }
+            grid,
+        })
     }
 
     pub fn get_galaxy(&self, number: u32) -> &Galaxy {
@@ -62,14 +56,8 @@ impl Universe {
     }
 
     pub fn expand(&self, times: usize) {
-        (0..self.width).rev().for_each(|x| {
-            if self
-                .galaxies
-                .iter()
-                .filter(|galaxy| galaxy.x.get() == x)
-                .count()
-                == 0
-            {
+        (0..self.grid.width()).rev().for_each(|x| {
+            if self.grid.column(x).all(|&char| char != '#') {
                 self.galaxies
                     .iter()
                     .filter(|galaxy| galaxy.x.get() > x)
@@ -77,14 +65,8 @@ impl Universe {
             }
         });
 
-        (0..self.height).rev().for_each(|y| {
-            if self
-                .galaxies
-                .iter()
-                .filter(|galaxy| galaxy.y.get() == y)
-                .count()
-                == 0
-            {
+        (0..self.grid.height()).rev().for_each(|y| {
+            if self.grid.row(y).iter().all(|&char| char != '#') {
                 self.galaxies
                     .iter()
                     .filter(|galaxy| galaxy.y.get() > y)
@@ -106,27 +88,40 @@ impl Universe {
     }
 }
 
-fn main() {
-    let universe1 = Universe::load(fs::read_to_string("inputs/day11.txt").unwrap());
-    universe1.expand(2);
+pub struct Day11;
 
-    // 10313550
-    println!("Part 1: {}", universe1.sum_shortest_paths());
+impl Solution for Day11 {
+    const DAY: u8 = 11;
 
-    let universe2 = Universe::load(fs::read_to_string("inputs/day11.txt").unwrap());
-    universe2.expand(1000000);
+    type Input = Universe;
+
+    fn parse(contents: &str) -> Result<Universe, ParseError> {
+        Universe::load(contents.to_string())
+    }
+
+    fn part1(universe: &Universe) -> impl Display {
+        let universe = universe.clone();
+        universe.expand(2);
+        universe.sum_shortest_paths()
+    }
 
-    // 611998089572
-    println!("Part 2: {}", universe2.sum_shortest_paths());
+    fn part2(universe: &Universe) -> impl Display {
+        let universe = universe.clone();
+        universe.expand(1000000);
+        universe.sum_shortest_paths()
+    }
 }
 
+pub const SOLVER: Solver = Solver::of::<Day11>();
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_part1() {
-        let universe = Universe::load(fs::read_to_string("samples/day11.txt").unwrap());
+        let universe = Universe::load(fs::read_to_string("samples/day11.txt").unwrap()).unwrap();
         universe.expand(2);
 
         assert_eq!(
@@ -146,12 +141,20 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let universe1 = Universe::load(fs::read_to_string("samples/day11.txt").unwrap());
+        let universe1 = Universe::load(fs::read_to_string("samples/day11.txt").unwrap()).unwrap();
         universe1.expand(10);
         assert_eq!(universe1.sum_shortest_paths(), 1030);
 
-        let universe2 = Universe::load(fs::read_to_string("samples/day11.txt").unwrap());
+        let universe2 = Universe::load(fs::read_to_string("samples/day11.txt").unwrap()).unwrap();
         universe2.expand(100);
         assert_eq!(universe2.sum_shortest_paths(), 8410);
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Universe::load("...#\n.#..\n#..*".to_string()).unwrap_err(),
+            ParseError::new(11, 3, 4, "'.' or '#'")
+        );
+    }
 }
@@ -0,0 +1,407 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    sequence: String,
+    groups: Vec<usize>,
+}
+
+impl Record {
+    pub fn load(contents: &str) -> Result<Self, ParseError> {
+        let line = Line::new(Day12::DAY, 1, contents);
+        let (sequence, groups) = line.split_once(" ", "' ' between springs and groups")?;
+        Line::new(line.day, line.number, sequence).expect_chars(".#?", "'.', '#' or '?'")?;
+
+        Ok(Self {
+            sequence: sequence.to_string(),
+            groups: groups
+                .split(',')
+                .map(|num| line.parse::<usize>(num, "group size"))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn is_match(&self) -> bool {
+        let broken_spring_parts = self.sequence.split('.').filter(|part| !part.is_empty());
+
+        if broken_spring_parts.clone().count() != self.groups.len() {
+            return false;
+        }
+
+        broken_spring_parts
+            .zip(&self.groups)
+            .filter(|(part, &group)| part.len() == group)
+            .count()
+            == self.groups.len()
+    }
+
+    pub fn expand(&self) -> Vec<Self> {
+        match self.sequence.chars().position(|char| char == '?') {
+            Some(pos) => [
+                Self {
+                    sequence: [&self.sequence[..pos], ".", &self.sequence[pos + 1..]].concat(),
+                    groups: self.groups.clone(),
+                }
+                .expand(),
+                Self {
+                    sequence: [&self.sequence[..pos], "#", &self.sequence[pos + 1..]].concat(),
+                    groups: self.groups.clone(),
+                }
+                .expand(),
+            ]
+            .concat(),
+            None => vec![self.clone()],
+        }
+    }
+
+    pub fn unfold(&self, times: usize) -> Self {
+        Self {
+            sequence: vec![self.sequence.as_str(); times].join("?"),
+            groups: self.groups.repeat(times),
+        }
+    }
+
+    pub fn arrangements(&self) -> usize {
+        self.count_arrangements(0, 0, &mut HashMap::new())
+    }
+
+    // Number of ways to place groups[group..] in sequence[pos..], memoised on (pos, group)
+    fn count_arrangements(
+        &self,
+        pos: usize,
+        group: usize,
+        counts: &mut HashMap<(usize, usize), usize>,
+    ) -> usize {
+        let springs = self.sequence.as_bytes();
+
+        // All groups placed: only valid if no broken springs(#) remain
+        if group == self.groups.len() {
+            return if springs[pos.min(springs.len())..].contains(&b'#') {
+                0
+            } else {
+                1
+            };
+        }
+
+        if pos >= springs.len() {
+            return 0;
+        }
+
+        if let Some(&count) = counts.get(&(pos, group)) {
+            return count;
+        }
+
+        let mut count = 0;
+        let size = self.groups[group];
+
+        // Operational spring(.) here, the group starts further on
+        if springs[pos] != b'#' {
+            count += self.count_arrangements(pos + 1, group, counts);
+        }
+
+        // Group starts here: needs room for its broken springs and may not be followed by another one
+        if springs[pos] != b'.'
+            && pos + size <= springs.len()
+            && !springs[pos..pos + size].contains(&b'.')
+            && springs.get(pos + size) != Some(&b'#')
+        {
+            count += self.count_arrangements(pos + size + 1, group + 1, counts);
+        }
+
+        counts.insert((pos, group), count);
+
+        count
+    }
+
+    // Lazily resolves every unknown spring(?), yielding only the arrangements matching the groups
+    pub fn resolved(&self) -> Arrangements<'_> {
+        Arrangements {
+            record: self,
+            counts: HashMap::new(),
+            stack: vec![(0, 0, String::new())],
+        }
+    }
+
+    // Page of the resolved arrangements, skipping offset arrangements without building them
+    pub fn solutions(&self, offset: usize, limit: usize) -> impl Iterator<Item = String> + '_ {
+        self.resolved().skip(offset).take(limit)
+    }
+}
+
+// Depth-first walk over partial arrangements as (position, group index, resolved prefix), in the
+// same order as expand(): an operational spring(.) before a broken one(#)
+pub struct Arrangements<'a> {
+    record: &'a Record,
+    counts: HashMap<(usize, usize), usize>,
+    stack: Vec<(usize, usize, String)>,
+}
+
+impl Arrangements<'_> {
+    fn count(&mut self, pos: usize, group: usize) -> usize {
+        self.record.count_arrangements(pos, group, &mut self.counts)
+    }
+
+    // Replaces a partial arrangement on the stack with its continuations that still have solutions
+    fn branch(&mut self, pos: usize, group: usize, prefix: String) {
+        let springs = self.record.sequence.as_bytes();
+        let size = self.record.groups[group];
+
+        if springs[pos] != b'.'
+            && pos + size <= springs.len()
+            && !springs[pos..pos + size].contains(&b'.')
+            && springs.get(pos + size) != Some(&b'#')
+            && self.count(pos + size + 1, group + 1) > 0
+        {
+            let separator = if pos + size < springs.len() { "." } else { "" };
+            let broken = [prefix.as_str(), &"#".repeat(size), separator].concat();
+
+            self.stack.push((pos + size + 1, group + 1, broken));
+        }
+
+        if springs[pos] != b'#' && self.count(pos + 1, group) > 0 {
+            self.stack.push((pos + 1, group, prefix + "."));
+        }
+    }
+
+    fn complete(&self, prefix: String) -> String {
+        let rest = self.record.sequence.len() - prefix.len();
+
+        prefix + &".".repeat(rest)
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.nth(0)
+    }
+
+    // Skips whole subtrees using their arrangement count, so a large offset stays cheap
+    fn nth(&mut self, mut n: usize) -> Option<String> {
+        while let Some((pos, group, prefix)) = self.stack.pop() {
+            let count = self.count(pos, group);
+
+            if n >= count {
+                n -= count;
+            } else if group == self.record.groups.len() {
+                return Some(self.complete(prefix));
+            } else {
+                self.branch(pos, group, prefix);
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    records: Vec<Record>,
+}
+
+impl Puzzle {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        Ok(Self {
+            records: contents
+                .lines()
+                .enumerate()
+                .map(|(i, line)| Record::load(line).map_err(|err| err.at_line(i + 1)))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn part1(&self) -> usize {
+        self.records
+            .iter()
+            .map(|record| record.arrangements())
+            .sum()
+    }
+
+    pub fn part2(&self) -> usize {
+        self.records
+            .iter()
+            .map(|record| record.unfold(5).arrangements())
+            .sum()
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Puzzle;
+
+    fn parse(contents: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::load(contents.to_string())
+    }
+
+    fn part1(puzzle: &Puzzle) -> impl Display {
+        puzzle.part1()
+    }
+
+    fn part2(puzzle: &Puzzle) -> impl Display {
+        puzzle.part2()
+    }
+}
+
+pub const SOLVER: Solver = Solver::of::<Day12>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Record::load("???.### 1,1,3").unwrap().arrangements(), 1);
+        assert_eq!(
+            Record::load(".??..??...?##. 1,1,3").unwrap().arrangements(),
+            4
+        );
+        assert_eq!(
+            Record::load("?#?#?#?#?#?#?#? 1,3,1,6")
+                .unwrap()
+                .arrangements(),
+            1
+        );
+        assert_eq!(
+            Record::load("????.#...#... 4,1,1").unwrap().arrangements(),
+            1
+        );
+        assert_eq!(
+            Record::load("????.######..#####. 1,6,5")
+                .unwrap()
+                .arrangements(),
+            4
+        );
+        assert_eq!(
+            Record::load("?###???????? 3,2,1").unwrap().arrangements(),
+            10
+        );
+
+        let sequence = Puzzle::load(fs::read_to_string("samples/day12.txt").unwrap()).unwrap();
+
+        assert_eq!(sequence.part1(), 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Record::load(".# 1").unwrap().unfold(5).sequence,
+            ".#?.#?.#?.#?.#"
+        );
+        assert_eq!(
+            Record::load(".# 1").unwrap().unfold(5).groups,
+            vec![1, 1, 1, 1, 1]
+        );
+
+        assert_eq!(
+            Record::load("???.### 1,1,3")
+                .unwrap()
+                .unfold(5)
+                .arrangements(),
+            1
+        );
+        assert_eq!(
+            Record::load(".??..??...?##. 1,1,3")
+                .unwrap()
+                .unfold(5)
+                .arrangements(),
+            16384
+        );
+        assert_eq!(
+            Record::load("????.######..#####. 1,6,5")
+                .unwrap()
+                .unfold(5)
+                .arrangements(),
+            2500
+        );
+        assert_eq!(
+            Record::load("?###???????? 3,2,1")
+                .unwrap()
+                .unfold(5)
+                .arrangements(),
+            506250
+        );
+
+        let puzzle = Puzzle::load(fs::read_to_string("samples/day12.txt").unwrap()).unwrap();
+
+        assert_eq!(puzzle.part2(), 525152);
+    }
+
+    #[test]
+    fn test_brute_force() {
+        // The memoised counter agrees with checking every expansion of the unknown springs
+        let puzzle = Puzzle::load(fs::read_to_string("samples/day12.txt").unwrap()).unwrap();
+
+        puzzle.records.iter().for_each(|record| {
+            assert_eq!(
+                record.arrangements(),
+                record
+                    .expand()
+                    .iter()
+                    .filter(|record| record.is_match())
+                    .count()
+            )
+        });
+    }
+
+    #[test]
+    fn test_resolved() {
+        let record = Record::load("?###???????? 3,2,1").unwrap();
+
+        assert_eq!(
+            record.resolved().take(3).collect::<Vec<_>>(),
+            vec![".###....##.#", ".###...##..#", ".###...##.#."]
+        );
+        assert_eq!(
+            record.solutions(8, 5).collect::<Vec<_>>(),
+            vec![".###.##..#..", ".###.##.#..."]
+        );
+        assert_eq!(record.resolved().nth(9), Some(".###.##.#...".to_string()));
+        assert_eq!(record.resolved().nth(10), None);
+        assert_eq!(Record::load("#.# 2").unwrap().resolved().next(), None);
+
+        // Same arrangements in the same order as filtering every expansion
+        let puzzle = Puzzle::load(fs::read_to_string("samples/day12.txt").unwrap()).unwrap();
+
+        puzzle.records.iter().for_each(|record| {
+            assert_eq!(
+                record.resolved().collect::<Vec<_>>(),
+                record
+                    .expand()
+                    .into_iter()
+                    .filter(|record| record.is_match())
+                    .map(|record| record.sequence)
+                    .collect::<Vec<_>>()
+            )
+        });
+
+        // Deep offsets into an unfolded record
+        let unfolded = record.unfold(5);
+
+        assert_eq!(
+            unfolded.solutions(506249, 10).collect::<Vec<_>>(),
+            vec![[".###.##.#..."; 5].join(".")]
+        );
+        assert_eq!(unfolded.resolved().nth(506250), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Puzzle::load("???.### 1,1,3\n.??..?x...?##. 1,1,3".to_string()).unwrap_err(),
+            ParseError::new(12, 2, 7, "'.', '#' or '?'")
+        );
+        assert_eq!(
+            Record::load("???.### 1,,3").unwrap_err(),
+            ParseError::new(12, 1, 11, "group size")
+        );
+    }
+}
@@ -1,38 +1,41 @@
-use std::fmt::Debug;
-use std::fs;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::fmt::{Debug, Display};
 
 #[derive(Debug)]
-struct Pattern {
-    contents: String,
-    rows: Vec<String>,
-    cols: Vec<String>,
+pub struct Pattern {
+    rows: Grid<char>,
+    cols: Grid<char>,
 }
 
 impl Pattern {
-    pub fn load(contents: String) -> Self {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        Ok(Self::new(Grid::parse(
+            Day13::DAY,
+            &contents,
+            |char| ".#".contains(char).then_some(char),
+            "'.' or '#'",
+        )?))
+    }
+
+    fn new(rows: Grid<char>) -> Self {
         Self {
-            contents: contents.to_string(),
-            rows: contents.lines().map(|line| line.to_string()).collect(),
-            cols: (0..contents.lines().nth(0).unwrap().len())
-                .map(|col| {
-                    contents
-                        .lines()
-                        .map(|line| line[col..col + 1].to_string())
-                        .collect::<String>()
-                })
-                .collect(),
+            cols: rows.transpose(),
+            rows,
         }
     }
 
     pub fn reflection_rows(&self) -> Vec<usize> {
-        self.reflection_indices(&self.rows)
+        self.reflection_indices(&self.rows.rows().collect::<Vec<&[char]>>())
             .iter()
             .map(|&index| index + 1)
             .collect()
     }
 
     pub fn reflection_cols(&self) -> Vec<usize> {
-        self.reflection_indices(&self.cols)
+        self.reflection_indices(&self.cols.rows().collect::<Vec<&[char]>>())
             .iter()
             .map(|&index| index + 1)
             .collect()
@@ -40,14 +43,13 @@ impl Pattern {
 
     // Returns (fixed summary, fixed pattern)
     pub fn fix_smudge(&self) -> (usize, Self) {
-        self.contents
-            .chars()
-            .enumerate()
-            .find_map(|(i, char)| match char {
+        self.rows
+            .iter()
+            .find_map(|(pos, &char)| match char {
                 '.' | '#' => {
-                    let mut fixed_contents = self.contents.clone();
-                    fixed_contents.replace_range(i..i + 1, if char == '.' { "#" } else { "." });
-                    let fixed_pattern = Self::load(fixed_contents);
+                    let mut fixed_rows = self.rows.clone();
+                    *fixed_rows.get_mut(pos).unwrap() = if char == '.' { '#' } else { '.' };
+                    let fixed_pattern = Self::new(fixed_rows);
 
                     let old_reflection_rows = self.reflection_rows();
                     let new_reflection_rows = fixed_pattern.reflection_rows();
@@ -90,7 +92,7 @@ impl Pattern {
             + self.reflection_cols().first().unwrap_or(&0usize)
     }
 
-    fn reflection_indices(&self, lines: &Vec<String>) -> Vec<usize> {
+    fn reflection_indices(&self, lines: &[&[char]]) -> Vec<usize> {
         lines[..lines.len() - 1] // All except the last (which has no following lines to compare with)
             .iter()
             .enumerate()
@@ -120,18 +122,28 @@ impl Pattern {
 }
 
 #[derive(Debug)]
-struct Puzzle {
+pub struct Puzzle {
     patterns: Vec<Pattern>,
 }
 
 impl Puzzle {
-    pub fn load(contents: String) -> Self {
-        Self {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        let mut first_line = 1;
+
+        Ok(Self {
             patterns: contents
                 .split("\n\n")
-                .map(|section| Pattern::load(section.to_string()))
-                .collect(),
-        }
+                .map(|section| {
+                    let pattern = Pattern::load(section.to_string()).map_err(|err| {
+                        let line = first_line + err.line - 1;
+                        err.at_line(line)
+                    });
+                    first_line += section.lines().count() + 1;
+
+                    pattern
+                })
+                .collect::<Result<_, _>>()?,
+        })
     }
 
     pub fn part1(&self) -> usize {
@@ -147,31 +159,41 @@ impl Puzzle {
     }
 }
 
-fn main() {
-    // 29846
-    println!(
-        "Part 1: {}",
-        Puzzle::load(fs::read_to_string("inputs/day13.txt").unwrap()).part1()
-    );
-    // 25401
-    println!(
-        "Part 2: {}",
-        Puzzle::load(fs::read_to_string("inputs/day13.txt").unwrap()).part2()
-    );
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Puzzle;
+
+    fn parse(contents: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::load(contents.to_string())
+    }
+
+    fn part1(puzzle: &Puzzle) -> impl Display {
+        puzzle.part1()
+    }
+
+    fn part2(puzzle: &Puzzle) -> impl Display {
+        puzzle.part2()
+    }
 }
 
+pub const SOLVER: Solver = Solver::of::<Day13>();
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_reflection() {
-        let puzzle = &Puzzle::load(fs::read_to_string("samples/day13a.txt").unwrap());
+        let puzzle = &Puzzle::load(fs::read_to_string("samples/day13a.txt").unwrap()).unwrap();
 
         assert_eq!(puzzle.patterns[0].reflection_cols(), vec![5]);
         assert_eq!(puzzle.patterns[1].reflection_rows(), vec![4]);
 
-        let puzzle = &Puzzle::load(fs::read_to_string("samples/day13b.txt").unwrap());
+        let puzzle = &Puzzle::load(fs::read_to_string("samples/day13b.txt").unwrap()).unwrap();
 
         assert_eq!(puzzle.patterns[0].reflection_rows(), vec![]);
         assert_eq!(puzzle.patterns[0].reflection_cols(), vec![3]);
@@ -188,14 +210,16 @@ mod tests {
     #[test]
     fn test_part1() {
         assert_eq!(
-            Puzzle::load(fs::read_to_string("samples/day13a.txt").unwrap()).part1(),
+            Puzzle::load(fs::read_to_string("samples/day13a.txt").unwrap())
+                .unwrap()
+                .part1(),
             405
         );
     }
 
     #[test]
     fn test_part2() {
-        let puzzle = &Puzzle::load(fs::read_to_string("samples/day13a.txt").unwrap());
+        let puzzle = &Puzzle::load(fs::read_to_string("samples/day13a.txt").unwrap()).unwrap();
 
         assert_eq!(puzzle.patterns[0].fix_smudge().1.reflection_rows(), vec![3]);
         assert_eq!(puzzle.patterns[0].fix_smudge().1.reflection_cols(), vec![5]); // still a valid reflection after fix
@@ -207,4 +231,12 @@ mod tests {
 
         assert_eq!(puzzle.part2(), 400);
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Puzzle::load("#.##\n..#.\n\n#..#\n#.O#".to_string()).unwrap_err(),
+            ParseError::new(13, 5, 3, "'.' or '#'")
+        );
+    }
 }
@@ -1,77 +1,54 @@
-use std::fmt::Debug;
-use std::fs;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::fmt::{Debug, Display};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Platform {
-    rows: Vec<String>,
+pub struct Platform {
+    grid: Grid<char>,
 }
 
 impl Platform {
-    pub fn load(contents: String) -> Self {
-        Self {
-            rows: contents
-                .trim()
-                .lines()
-                .map(|line| line.to_string())
-                .collect(),
-        }
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        Ok(Self {
+            grid: Grid::parse(
+                Day14::DAY,
+                &contents,
+                |char| ".#O".contains(char).then_some(char),
+                "'.', '#' or 'O'",
+            )?,
+        })
     }
 
     pub fn rotate_ccw(&self) -> Self {
         Self {
-            rows: (0..self.rows[0].len())
-                .rev()
-                .map(|col| {
-                    self.rows
-                        .iter()
-                        .map(|line| line[col..col + 1].to_string())
-                        .collect::<String>()
-                })
-                .collect(),
+            grid: self.grid.rotate_ccw(),
         }
     }
 
     pub fn rotate_cw(&self) -> Self {
         Self {
-            rows: (0..self.rows[0].len())
-                .map(|col| {
-                    self.rows
-                        .iter()
-                        .map(|line| line[col..col + 1].to_string())
-                        .rev()
-                        .collect::<String>()
-                })
-                .collect(),
+            grid: self.grid.rotate_cw(),
         }
     }
 
     pub fn move_boulders_left(&self) -> Self {
         Self {
-            rows: self
-                .rows
-                .iter()
-                .map(|row| {
-                    row.chars()
-                        .enumerate()
-                        .fold(String::new(), |mut acc, (i, char)| {
-                            if char == '#' {
-                                // push all occurrences of rocks(#) unaltered
-                                acc.push(char);
-                            }
-                            // First boulder(O) or open space(.), or one preceded by a rock (#)
-                            else if i == 0 || &row[i.saturating_sub(1)..i] == "#" {
-                                let mut captured = row[i..]
-                                    .chars()
-                                    .take_while(|&cap_char| cap_char != '#')
-                                    .collect::<Vec<char>>();
-                                captured.sort_by(|a, b| b.cmp(a));
-                                acc.push_str(&captured.iter().collect::<String>());
-                            }
-
-                            acc
-                        })
-                })
-                .collect(),
+            grid: Grid::from_rows(
+                self.grid
+                    .rows()
+                    .map(|row| {
+                        let mut row = row.to_vec();
+
+                        // Sort the boulders(O) before the open space(.) in between the rocks(#)
+                        row.split_mut(|&char| char == '#')
+                            .for_each(|captured| captured.sort_by(|a, b| b.cmp(a)));
+
+                        row
+                    })
+                    .collect(),
+            ),
         }
     }
 
@@ -104,34 +81,45 @@ impl Platform {
     }
 
     pub fn total_load_north(&self) -> usize {
-        self.rows
-            .iter()
-            .zip((0..self.rows.len() + 1).rev())
+        self.grid
+            .rows()
+            .zip((0..self.grid.height() + 1).rev())
             .fold(0, |acc, (row, weight)| {
-                acc + row.chars().filter(|&char| char == 'O').count() * weight
+                acc + row.iter().filter(|&&char| char == 'O').count() * weight
             })
     }
 }
 
-fn main() {
-    let platform = Platform::load(fs::read_to_string("inputs/day14.txt").unwrap());
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
 
-    // 113424
-    println!("Part 1: {}", platform.slide_north().total_load_north());
-    // 96003
-    println!(
-        "Part 2: {}",
+    type Input = Platform;
+
+    fn parse(contents: &str) -> Result<Platform, ParseError> {
+        Platform::load(contents.to_string())
+    }
+
+    fn part1(platform: &Platform) -> impl Display {
+        platform.slide_north().total_load_north()
+    }
+
+    fn part2(platform: &Platform) -> impl Display {
         platform.slide_cycle(1_000_000_000).total_load_north()
-    );
+    }
 }
 
+pub const SOLVER: Solver = Solver::of::<Day14>();
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_common() {
-        let platform = Platform::load(fs::read_to_string("samples/day14.txt").unwrap());
+        let platform = Platform::load(fs::read_to_string("samples/day14.txt").unwrap()).unwrap();
 
         assert_eq!(
             Platform::load(
@@ -149,6 +137,7 @@ mod tests {
         "#
                 .to_string()
             )
+            .unwrap()
             .total_load_north(),
             64
         );
@@ -185,6 +174,7 @@ O..O#...O.
 "#
                 .to_string()
             )
+            .unwrap()
         );
 
         // Test given cycles
@@ -205,6 +195,7 @@ O..O#...O.
 "#
                 .to_string()
             )
+            .unwrap()
         );
 
         assert_eq!(
@@ -224,6 +215,7 @@ O..O#...O.
             "#
                 .to_string()
             )
+            .unwrap()
         );
 
         assert_eq!(
@@ -243,6 +235,7 @@ O..O#...O.
 "#
                 .to_string()
             )
+            .unwrap()
         );
     }
 
@@ -250,6 +243,7 @@ O..O#...O.
     fn test_part1() {
         assert_eq!(
             Platform::load(fs::read_to_string("samples/day14.txt").unwrap())
+                .unwrap()
                 .slide_north()
                 .total_load_north(),
             136
@@ -260,9 +254,18 @@ O..O#...O.
     fn test_part2() {
         assert_eq!(
             Platform::load(fs::read_to_string("samples/day14.txt").unwrap())
+                .unwrap()
                 .slide_cycle(1_000_000_000)
                 .total_load_north(),
             64
         );
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Platform::load("O.#.\n.O.\n".to_string()).unwrap_err(),
+            ParseError::new(14, 2, 4, "row of 4 tiles")
+        );
+    }
 }
@@ -0,0 +1,200 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+#[derive(Debug)]
+pub struct Step {
+    line: String,
+    operation: Operation,
+}
+
+impl Step {
+    pub fn load(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            line: input.to_string(),
+            operation: Operation::load(input)?,
+        })
+    }
+
+    pub fn operation(&self) -> &Operation {
+        &self.operation
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Operation {
+    Remove { label: String },
+    Insert { label: String, focal_length: u8 },
+}
+
+impl Operation {
+    pub fn load(input: &str) -> Result<Self, ParseError> {
+        let line = Line::new(Day15::DAY, 1, input);
+
+        match input.split_once('=') {
+            Some((label, focal_length)) => Ok(Self::Insert {
+                label: label.to_string(),
+                focal_length: line.parse::<u8>(focal_length, "focal length")?,
+            }),
+            None => Ok(Self::Remove {
+                label: input
+                    .strip_suffix('-')
+                    .ok_or_else(|| line.error_at_end("'=' or '-'"))?
+                    .to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Sequence {
+    steps: Vec<Step>,
+}
+
+impl Sequence {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        let line = Line::new(Day15::DAY, 1, contents.trim_end());
+
+        Ok(Self {
+            steps: line
+                .text
+                .split(',')
+                .map(|step| {
+                    Step::load(step).map_err(|err| err.offset_columns(line.column(step) - 1))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn hash(&self, input: &str) -> u8 {
+        input
+            .chars()
+            .fold(0, |acc, char| ((acc + char as u16) * 17) % 256) as u8
+    }
+
+    pub fn part1(&self) -> usize {
+        self.steps
+            .iter()
+            .fold(0, |acc, step| acc + self.hash(&step.line) as usize)
+    }
+
+    pub fn part2(&self) -> usize {
+        let mut boxes: HashMap<u8, Vec<(String, u8)>> = HashMap::new();
+
+        // Install lenses by processing steps
+        self.steps.iter().for_each(|step| match step.operation() {
+            Operation::Remove { label } => {
+                if let Some(b) = boxes.get_mut(&self.hash(label)) {
+                    b.retain(|(lens_label, _)| lens_label != label);
+                }
+            }
+            Operation::Insert {
+                label,
+                focal_length,
+            } => {
+                let b = boxes.entry(self.hash(label)).or_insert(vec![]);
+
+                match b.iter().position(|(lens_label, _)| lens_label == label) {
+                    Some(pos) => b[pos] = (label.clone(), *focal_length),
+                    None => b.push((label.clone(), *focal_length)),
+                };
+            }
+        });
+
+        // Add up focusing power of the lenses
+        boxes.iter().fold(0, |acc, (i_box, b)| {
+            acc + b
+                .iter()
+                .enumerate()
+                .fold(0, |acc_box, (i_slot, (_, focal_length))| {
+                    acc_box + (*i_box as usize + 1) * (i_slot + 1) * *focal_length as usize
+                })
+        })
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Sequence;
+
+    fn parse(contents: &str) -> Result<Sequence, ParseError> {
+        Sequence::load(contents.to_string())
+    }
+
+    fn part1(sequence: &Sequence) -> impl Display {
+        sequence.part1()
+    }
+
+    fn part2(sequence: &Sequence) -> impl Display {
+        sequence.part2()
+    }
+}
+
+pub const SOLVER: Solver = Solver::of::<Day15>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_part1() {
+        let sequence = Sequence::load(fs::read_to_string("samples/day15.txt").unwrap()).unwrap();
+
+        assert_eq!(sequence.hash("HASH"), 52);
+        assert_eq!(sequence.hash("rn=1"), 30);
+        assert_eq!(sequence.hash("cm-"), 253);
+        assert_eq!(sequence.hash("qp=3"), 97);
+        assert_eq!(sequence.hash("cm=2"), 47);
+        assert_eq!(sequence.hash("qp-"), 14);
+        assert_eq!(sequence.hash("pc=4"), 180);
+        assert_eq!(sequence.hash("ot=9"), 9);
+        assert_eq!(sequence.hash("ab=5"), 197);
+        assert_eq!(sequence.hash("pc-"), 48);
+        assert_eq!(sequence.hash("pc=6"), 214);
+        assert_eq!(sequence.hash("ot=7"), 231);
+
+        assert_eq!(sequence.part1(), 1320);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Step::load("rn=1").unwrap().operation(),
+            &Operation::Insert {
+                label: "rn".to_string(),
+                focal_length: 1
+            }
+        );
+        assert_eq!(
+            Step::load("cm-").unwrap().operation(),
+            &Operation::Remove {
+                label: "cm".to_string(),
+            }
+        );
+
+        assert_eq!(
+            Sequence::load(fs::read_to_string("samples/day15.txt").unwrap())
+                .unwrap()
+                .part2(),
+            145
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Sequence::load("rn=1,cm-,qp=x".to_string()).unwrap_err(),
+            ParseError::new(15, 1, 13, "focal length")
+        );
+        assert_eq!(
+            Sequence::load("rn=1,cm,qp=3".to_string()).unwrap_err(),
+            ParseError::new(15, 1, 8, "'=' or '-'")
+        );
+    }
+}
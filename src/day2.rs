@@ -0,0 +1,589 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+// Default colours of the cubes in the bag, see State::with_colours for others
+pub const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CubeGame {
+    id: u32,
+    sets: Vec<CubeSet>,
+}
+
+impl CubeGame {
+    pub fn new(id: u32, sets: Vec<CubeSet>) -> Self {
+        Self { id, sets }
+    }
+
+    pub fn superset(&self) -> CubeSet {
+        self.sets
+            .iter()
+            .fold(CubeSet::default(), |superset, set| superset.union(set))
+    }
+
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
+        self.sets.iter().all(|set| set.is_subset(bag))
+    }
+
+    // Every draw that needs more cubes of a colour than the bag holds
+    pub fn violations(&self, bag: &CubeSet) -> Vec<Violation> {
+        self.sets
+            .iter()
+            .enumerate()
+            .flat_map(|(i, set)| {
+                set.counts
+                    .iter()
+                    .filter(|(colour, &drawn)| drawn > bag.count(colour))
+                    .map(move |(colour, &drawn)| Violation {
+                        set: i + 1,
+                        colour: colour.to_string(),
+                        drawn,
+                        available: bag.count(colour),
+                    })
+            })
+            .collect()
+    }
+}
+
+// Same format as the input: "Game 1: 3 blue, 4 red; 1 red, 2 green", or "Game 1:" without any sets
+impl Display for CubeGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.sets.is_empty() {
+            return write!(f, "Game {}:", self.id);
+        }
+
+        write!(
+            f,
+            "Game {}: {}",
+            self.id,
+            self.sets
+                .iter()
+                .map(|set| set.to_string())
+                .collect::<Vec<String>>()
+                .join("; ")
+        )
+    }
+}
+
+// Reason a bag rules out a game: one of its sets draws more cubes of a colour than there are
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Violation {
+    pub set: usize,
+    pub colour: String,
+    pub drawn: u32,
+    pub available: u32,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "set {} draws {} {} but the bag holds {}",
+            self.set, self.drawn, self.colour, self.available
+        )
+    }
+}
+
+// Number of cubes per colour, where a missing colour counts as 0 cubes
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CubeSet {
+    counts: BTreeMap<String, u32>,
+}
+
+impl CubeSet {
+    pub fn from_counts(counts: &[(&str, u32)]) -> Self {
+        let mut set = Self::default();
+        counts
+            .iter()
+            .for_each(|&(colour, count)| set.set(colour, count));
+
+        set
+    }
+
+    pub fn count(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    // Leaves out colours without cubes, so equal sets compare equal
+    pub fn set(&mut self, colour: &str, count: u32) {
+        if count == 0 {
+            self.counts.remove(colour);
+        } else {
+            self.counts.insert(colour.to_string(), count);
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.counts
+            .iter()
+            .all(|(colour, &count)| count <= other.count(colour))
+    }
+
+    // Largest count of each colour in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+
+        other.counts.iter().for_each(|(colour, &count)| {
+            union.set(colour, count.max(self.count(colour)));
+        });
+
+        union
+    }
+
+    // Product of the counts of the given colours, so 0 if any of them is missing
+    pub fn power(&self, colours: &[String]) -> u32 {
+        colours.iter().map(|colour| self.count(colour)).product()
+    }
+}
+
+// Colours in alphabetical order, as "3 blue, 4 red"
+impl Display for CubeSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.counts
+                .iter()
+                .map(|(colour, count)| format!("{} {}", count, colour))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct State {
+    colours: Vec<String>,
+    games: Vec<CubeGame>,
+}
+
+impl State {
+    pub fn from_string(contents: String) -> Result<Self, ParseError> {
+        Self::with_colours(contents, &COLOURS)
+    }
+
+    // Parses games that may only draw cubes of the given colours
+    pub fn with_colours(contents: String, colours: &[&str]) -> Result<Self, ParseError> {
+        let expected_colour = match colours {
+            [] => "no cubes".to_string(),
+            [colour] => format!("colour '{}'", colour),
+            [colours @ .., last] => {
+                format!("colour '{}' or '{}'", colours.join("', '"), last)
+            }
+        };
+
+        Ok(Self {
+            colours: colours.iter().map(|colour| colour.to_string()).collect(),
+            // Lines aren't trimmed, as a trailing empty set ends in a space
+            games: Line::all(Day2::DAY, &contents)
+                .filter(|line| !line.text.trim().is_empty())
+                .map(|line| {
+                    let (col_game, col_sets) = line.split_once(":", "': ' after the game id")?;
+                    let id = col_game
+                        .strip_prefix("Game ")
+                        .ok_or_else(|| line.error(col_game, "'Game '"))?;
+                    let id = line.parse::<u32>(id, "game id")?;
+
+                    // A game without any sets ends right after the colon
+                    if col_sets.is_empty() {
+                        return Ok(CubeGame { id, sets: vec![] });
+                    }
+
+                    Ok(CubeGame {
+                        id,
+                        sets: col_sets
+                            .strip_prefix(' ')
+                            .ok_or_else(|| line.error(col_sets, "': ' after the game id"))?
+                            .split("; ")
+                            .map(|col_set| {
+                                let mut set = CubeSet::default();
+                                let mut drawn: Vec<&str> = vec![];
+
+                                // A set without any cubes prints as nothing at all
+                                if col_set.is_empty() {
+                                    return Ok(set);
+                                }
+
+                                for col_color in col_set.split(", ") {
+                                    let (number, color) =
+                                        col_color.split_once(' ').ok_or_else(|| {
+                                            line.error(col_color, "'<count> <colour>'")
+                                        })?;
+                                    let number = line.parse::<u32>(number, "cube count")?;
+
+                                    if !colours.contains(&color) {
+                                        return Err(line.error(color, &expected_colour));
+                                    }
+
+                                    // A second count for the same colour would overwrite the first
+                                    if drawn.contains(&color) {
+                                        return Err(
+                                            line.error(color, "colour that isn't in this set yet")
+                                        );
+                                    }
+
+                                    drawn.push(color);
+                                    set.set(color, number);
+                                }
+
+                                Ok(set)
+                            })
+                            .collect::<Result<_, _>>()?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn colours(&self) -> &[String] {
+        &self.colours
+    }
+
+    pub fn games(&self) -> &[CubeGame] {
+        &self.games
+    }
+
+    pub fn sum_possible_game_ids(&self, bag: &CubeSet) -> u32 {
+        self.games
+            .iter()
+            .filter(|game| game.is_possible(bag))
+            .map(|game| game.id)
+            .sum()
+    }
+
+    pub fn sum_game_powers(&self) -> u32 {
+        self.games
+            .iter()
+            .map(|game| game.superset().power(&self.colours))
+            .sum()
+    }
+
+    pub fn possible_games(&self, bag: &CubeSet) -> Vec<&CubeGame> {
+        self.games
+            .iter()
+            .filter(|game| game.is_possible(bag))
+            .collect()
+    }
+
+    // Ids of the games a bag rules out, with every reason why
+    pub fn ruled_out(&self, bag: &CubeSet) -> Vec<(u32, Vec<Violation>)> {
+        self.games
+            .iter()
+            .map(|game| (game.id, game.violations(bag)))
+            .filter(|(_, violations)| !violations.is_empty())
+            .collect()
+    }
+
+    // Bags that can't lose a single cube without ruling out another game, by increasing size.
+    // Any bag shrinks onto one of these while still admitting the same games.
+    // These are exactly the unions of game supersets, so they are grown one superset at a time,
+    // which takes time in proportion to the size of the frontier times the number of games.
+    pub fn frontier(&self) -> Vec<CubeSet> {
+        let supersets: BTreeSet<CubeSet> = self.games.iter().map(|game| game.superset()).collect();
+        let mut frontier = BTreeSet::from([CubeSet::default()]);
+        let mut pending = vec![CubeSet::default()];
+
+        while let Some(bag) = pending.pop() {
+            supersets
+                .iter()
+                .filter(|superset| !superset.is_subset(&bag))
+                .for_each(|superset| {
+                    let grown = bag.union(superset);
+
+                    if frontier.insert(grown.clone()) {
+                        pending.push(grown);
+                    }
+                });
+        }
+
+        let mut frontier: Vec<CubeSet> = frontier.into_iter().collect();
+        frontier.sort_by_key(|bag| bag.total());
+
+        frontier
+    }
+
+    // Bag with the fewest cubes that admits at least k games, if there are that many
+    pub fn min_bag(&self, k: usize) -> Option<CubeSet> {
+        self.frontier()
+            .into_iter()
+            .find(|bag| self.possible_games(bag).len() >= k)
+    }
+}
+
+impl Display for State {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.games
+                .iter()
+                .map(|game| game.to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = State;
+
+    fn parse(contents: &str) -> Result<State, ParseError> {
+        State::from_string(contents.to_string())
+    }
+
+    fn part1(state: &State) -> impl Display {
+        state.sum_possible_game_ids(&CubeSet::from_counts(&[
+            ("red", 12),
+            ("green", 13),
+            ("blue", 14),
+        ]))
+    }
+
+    fn part2(state: &State) -> impl Display {
+        state.sum_game_powers()
+    }
+}
+
+pub const SOLVER: Solver = Solver::of::<Day2>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const SAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_part1() {
+        let state = State::from_string(SAMPLE.to_string()).unwrap();
+        let bag = CubeSet::from_counts(&[("red", 12), ("green", 13), ("blue", 14)]);
+
+        assert!(state.games[0].is_possible(&bag));
+        assert!(!state.games[2].is_possible(&bag));
+        assert_eq!(state.sum_possible_game_ids(&bag), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        let state = State::from_string(SAMPLE.to_string()).unwrap();
+
+        assert_eq!(
+            state.games[0].superset(),
+            CubeSet::from_counts(&[("red", 4), ("green", 2), ("blue", 6)])
+        );
+        assert_eq!(state.sum_game_powers(), 2286);
+    }
+
+    #[test]
+    fn test_colours() {
+        let state = State::with_colours(
+            "Game 1: 2 cyan, 1 red; 3 magenta\nGame 2: 4 cyan".to_string(),
+            &["cyan", "magenta", "red"],
+        )
+        .unwrap();
+        let bag = CubeSet::from_counts(&[("cyan", 3), ("magenta", 3)]);
+
+        assert_eq!(state.games[0].superset().count("magenta"), 3);
+        assert_eq!(state.games[0].superset().power(state.colours()), 6);
+        assert_eq!(state.games[1].superset().power(state.colours()), 0);
+        assert!(!state.games[0].is_possible(&bag));
+        assert!(!state.games[1].is_possible(&bag));
+        assert!(state.games[1].is_possible(&CubeSet::from_counts(&[("cyan", 4)])));
+    }
+
+    #[test]
+    fn test_ruled_out() {
+        let state = State::from_string(SAMPLE.to_string()).unwrap();
+        let ruled_out = state.ruled_out(&CubeSet::from_counts(&[
+            ("red", 12),
+            ("green", 13),
+            ("blue", 14),
+        ]));
+
+        assert_eq!(
+            ruled_out.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(
+            ruled_out[0].1,
+            vec![Violation {
+                set: 1,
+                colour: "red".to_string(),
+                drawn: 20,
+                available: 12
+            }]
+        );
+        assert_eq!(
+            ruled_out[1].1[0].to_string(),
+            "set 3 draws 15 blue but the bag holds 14"
+        );
+        assert_eq!(ruled_out[1].1.len(), 2);
+        assert_eq!(state.ruled_out(&CubeSet::default()).len(), 5);
+    }
+
+    #[test]
+    fn test_frontier() {
+        let state = State::from_string(SAMPLE.to_string()).unwrap();
+        let frontier = state.frontier();
+
+        assert_eq!(frontier.first(), Some(&CubeSet::default()));
+        assert_eq!(
+            frontier.last(),
+            Some(&CubeSet::from_counts(&[
+                ("red", 20),
+                ("green", 13),
+                ("blue", 15)
+            ]))
+        );
+
+        // Taking away any cube rules out a game
+        frontier.iter().for_each(|bag| {
+            bag.counts.iter().for_each(|(colour, &count)| {
+                let mut smaller = bag.clone();
+                smaller.set(colour, count - 1);
+
+                assert!(state.possible_games(&smaller).len() < state.possible_games(bag).len());
+            })
+        });
+
+        assert_eq!(state.min_bag(0), Some(CubeSet::default()));
+        assert_eq!(
+            state.min_bag(1),
+            Some(CubeSet::from_counts(&[
+                ("red", 1),
+                ("green", 3),
+                ("blue", 4)
+            ]))
+        );
+        assert_eq!(state.min_bag(2).unwrap().total(), 13);
+        assert_eq!(
+            state.min_bag(3),
+            Some(CubeSet::from_counts(&[
+                ("red", 6),
+                ("green", 3),
+                ("blue", 6)
+            ]))
+        );
+        assert_eq!(state.min_bag(5), frontier.last().cloned());
+        assert_eq!(state.min_bag(6), None);
+
+        // Many colours don't blow up the frontier, which only depends on the games
+        let colours: Vec<String> = (0..30).map(|i| format!("c{}", i)).collect();
+        let game = |id: u32, count: u32| {
+            let draws: Vec<String> = colours
+                .iter()
+                .enumerate()
+                .map(|(i, colour)| format!("{} {}", count + i as u32 % 3, colour))
+                .collect();
+            format!("Game {}: {}", id, draws.join(", "))
+        };
+        let contents = [game(1, 1), game(2, 2), game(3, 5)].join("\n");
+        let colour_names: Vec<&str> = colours.iter().map(|colour| colour.as_str()).collect();
+        let state = State::with_colours(contents, &colour_names).unwrap();
+
+        assert_eq!(state.frontier().len(), 4);
+    }
+
+    #[test]
+    fn test_display() {
+        let game = CubeGame::new(
+            7,
+            vec![
+                CubeSet::from_counts(&[("red", 4), ("blue", 3)]),
+                CubeSet::default(),
+                CubeSet::from_counts(&[("green", 2), ("red", 0)]),
+            ],
+        );
+
+        assert_eq!(game.to_string(), "Game 7: 3 blue, 4 red; ; 2 green");
+        assert_eq!(
+            State::from_string(game.to_string()).unwrap().games(),
+            &[game]
+        );
+
+        // Empty sets at the end, and no sets at all
+        let games = [
+            CubeGame::new(
+                1,
+                vec![CubeSet::from_counts(&[("red", 1)]), CubeSet::default()],
+            ),
+            CubeGame::new(2, vec![]),
+            CubeGame::new(3, vec![CubeSet::default()]),
+            CubeGame::new(4, vec![CubeSet::default(), CubeSet::default()]),
+        ];
+        let printed = games
+            .iter()
+            .map(|game| game.to_string())
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        assert_eq!(printed, "Game 1: 1 red; \nGame 2:\nGame 3: \nGame 4: ; ");
+        assert_eq!(State::from_string(printed + "\n").unwrap().games(), &games);
+
+        let state = State::from_string(SAMPLE.to_string()).unwrap();
+
+        assert_eq!(
+            state.to_string().lines().next(),
+            Some("Game 1: 3 blue, 4 red; 6 blue, 2 green, 1 red; 2 green")
+        );
+
+        // Printing and parsing the real input gives back the same games
+        let state = State::from_string(fs::read_to_string("inputs/day2.txt").unwrap()).unwrap();
+
+        assert_eq!(State::from_string(state.to_string()).unwrap(), state);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            State::from_string(fs::read_to_string("inputs/day2.txt").unwrap())
+                .unwrap()
+                .games
+                .len(),
+            100
+        );
+        assert_eq!(
+            State::from_string("Game 1: 3 blue\nGame 2: 1 red; x green".to_string()).unwrap_err(),
+            ParseError::new(2, 2, 16, "cube count")
+        );
+        assert_eq!(
+            State::from_string("Game 1 3 blue".to_string()).unwrap_err(),
+            ParseError::new(2, 1, 14, "': ' after the game id")
+        );
+        assert_eq!(
+            State::from_string("Game 1:3 blue".to_string()).unwrap_err(),
+            ParseError::new(2, 1, 8, "': ' after the game id")
+        );
+        assert_eq!(
+            State::from_string("Game 1: 3 blue, 2 yellow".to_string()).unwrap_err(),
+            ParseError::new(2, 1, 19, "colour 'red', 'green' or 'blue'")
+        );
+        assert_eq!(
+            State::with_colours("Game 1: 3 blue".to_string(), &["cyan"]).unwrap_err(),
+            ParseError::new(2, 1, 11, "colour 'cyan'")
+        );
+        assert_eq!(
+            State::from_string("Game 1: 1 red; 3 blue, 2 blue".to_string()).unwrap_err(),
+            ParseError::new(2, 1, 26, "colour that isn't in this set yet")
+        );
+        assert_eq!(
+            State::from_string("Game 1: 0 blue, 2 blue".to_string()).unwrap_err(),
+            ParseError::new(2, 1, 19, "colour that isn't in this set yet")
+        );
+    }
+}
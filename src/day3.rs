@@ -0,0 +1,412 @@
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use num::BigUint;
+use std::fmt::Display;
+#[cfg(test)]
+use std::fs;
+use std::ops::RangeInclusive;
+
+#[derive(Debug)]
+pub struct Schematic {
+    parts: Vec<Part>,
+    // Index into parts of the part covering each tile
+    index: Grid<Option<usize>>,
+}
+
+impl Schematic {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        Self::parse(&contents, false)
+    }
+
+    // Lets numbers at the end of a row carry on at the start of the next row
+    pub fn load_wrapped(contents: String) -> Result<Self, ParseError> {
+        Self::parse(&contents, true)
+    }
+
+    fn parse(contents: &str, wrapped: bool) -> Result<Self, ParseError> {
+        let grid = Grid::parse(Day3::DAY, contents, Some, "schematic tile")?;
+        let tiles: Vec<(Pos, char)> = grid.iter().map(|(pos, &char)| (pos, char)).collect();
+
+        // Whether the tile at an index in reading order follows on from a digit before it
+        let continues = |i: usize| {
+            let ((x, _), _) = tiles[i];
+            i > 0 && tiles[i - 1].1.is_ascii_digit() && (wrapped || x > 0)
+        };
+        let mut parts: Vec<Part> = vec![];
+
+        for (i, &(pos, char)) in tiles.iter().enumerate() {
+            if char == '.' || (char.is_ascii_digit() && continues(i)) {
+                continue;
+            }
+
+            if !char.is_ascii_digit() {
+                parts.push(Part {
+                    tiles: vec![pos],
+                    kind: PartKind::Symbol(char),
+                });
+                continue;
+            }
+
+            // Digits from here on, for as long as the number continues
+            let end = (i + 1..tiles.len())
+                .find(|&j| !tiles[j].1.is_ascii_digit() || !continues(j))
+                .unwrap_or(tiles.len());
+            let digits: String = tiles[i..end].iter().map(|&(_, char)| char).collect();
+            let num = digits.parse::<u64>().map_err(|_| {
+                ParseError::new(
+                    Day3::DAY,
+                    pos.1 + 1,
+                    pos.0 + 1,
+                    &format!("part number up to {}", u64::MAX),
+                )
+            })?;
+
+            parts.push(Part {
+                tiles: tiles[i..end].iter().map(|&(pos, _)| pos).collect(),
+                kind: PartKind::Number(num),
+            });
+        }
+
+        let mut index = grid.map(|_, _| None);
+        parts.iter().enumerate().for_each(|(i, part)| {
+            part.tiles
+                .iter()
+                .for_each(|&pos| *index.get_mut(pos).unwrap() = Some(i));
+        });
+
+        Ok(Self { parts, index })
+    }
+
+    // Parts on the tiles surrounding any tile of the origin part, in reading order
+    fn neighbour_parts(&self, origin_part: &Part) -> Vec<&Part> {
+        let origin = self.index.get(origin_part.pos()).copied().flatten();
+        let mut neighbours: Vec<usize> = origin_part
+            .tiles
+            .iter()
+            .flat_map(|&pos| self.index.neighbours_8(pos))
+            .filter_map(|pos| *self.index.get(pos).unwrap())
+            .filter(|&i| Some(i) != origin)
+            .collect();
+        neighbours.sort();
+        neighbours.dedup();
+
+        neighbours.iter().map(|&i| &self.parts[i]).collect()
+    }
+
+    // Sum of the numbers next to a symbol, which can outgrow a u64 even though each number fits
+    pub fn part_number_sum(&self) -> u128 {
+        self.parts
+            .iter()
+            .filter_map(|part| match part.kind {
+                PartKind::Number(num) => Some((part, num)),
+                _ => None,
+            })
+            .filter(|(part, _)| {
+                self.neighbour_parts(part)
+                    .iter()
+                    .any(|neighbour| matches!(neighbour.kind, PartKind::Symbol(_)))
+            })
+            .map(|(_, num)| num as u128)
+            .sum()
+    }
+
+    // Symbols matching the rule with the numbers around them
+    pub fn gears(&self, rule: &GearRule) -> Vec<Gear<'_>> {
+        self.parts
+            .iter()
+            .filter(|part| {
+                matches!(part.kind, PartKind::Symbol(symbol) if rule.symbols.contains(&symbol))
+            })
+            .filter_map(|symbol| {
+                let parts: Vec<&Part> = self
+                    .neighbour_parts(symbol)
+                    .into_iter()
+                    .filter(|neighbour| matches!(neighbour.kind, PartKind::Number(_)))
+                    .collect();
+
+                rule.counts.contains(&parts.len()).then(|| Gear {
+                    value: rule.combine.apply(parts.iter().filter_map(|part| part.number())),
+                    symbol,
+                    parts,
+                })
+            })
+            .collect()
+    }
+
+    pub fn gear_sum(&self, rule: &GearRule) -> BigUint {
+        self.gears(rule).iter().map(|gear| &gear.value).sum()
+    }
+
+    pub fn gear_ratio_sum(&self) -> BigUint {
+        self.gear_sum(&GearRule::ratio())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Combine {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Combine {
+    // Exact whatever the numbers, as even a product of two u64 numbers doesn't fit a u64
+    fn apply(&self, numbers: impl Iterator<Item = u64>) -> BigUint {
+        let numbers = numbers.map(BigUint::from);
+
+        match self {
+            Combine::Product => numbers.product(),
+            Combine::Sum => numbers.sum(),
+            Combine::Max => numbers.max().unwrap_or_default(),
+        }
+    }
+}
+
+// Which symbols are gears, how many numbers they need around them and how these make up their value
+#[derive(Debug, Clone)]
+pub struct GearRule {
+    symbols: Vec<char>,
+    counts: RangeInclusive<usize>,
+    combine: Combine,
+}
+
+impl GearRule {
+    pub fn new(symbols: &str, counts: RangeInclusive<usize>, combine: Combine) -> Self {
+        Self {
+            symbols: symbols.chars().collect(),
+            counts,
+            combine,
+        }
+    }
+
+    // Puzzle rule: a '*' next to exactly two numbers, multiplied together
+    pub fn ratio() -> Self {
+        Self::new("*", 2..=2, Combine::Product)
+    }
+}
+
+#[derive(Debug)]
+pub struct Gear<'a> {
+    pub symbol: &'a Part,
+    pub parts: Vec<&'a Part>,
+    pub value: BigUint,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum PartKind {
+    Number(u64),
+    Symbol(char),
+}
+
+#[derive(Debug)]
+pub struct Part {
+    // Tiles the part takes up in reading order, including any leading zeros of a number
+    tiles: Vec<Pos>,
+    kind: PartKind,
+}
+
+impl Part {
+    pub fn pos(&self) -> Pos {
+        self.tiles[0]
+    }
+
+    pub fn tiles(&self) -> &[Pos] {
+        &self.tiles
+    }
+
+    pub fn kind(&self) -> &PartKind {
+        &self.kind
+    }
+
+    pub fn number(&self) -> Option<u64> {
+        match self.kind {
+            PartKind::Number(num) => Some(num),
+            _ => None,
+        }
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+
+    fn parse(contents: &str) -> Result<Schematic, ParseError> {
+        Schematic::load(contents.to_string())
+    }
+
+    fn part1(schematic: &Schematic) -> impl Display {
+        schematic.part_number_sum()
+    }
+
+    fn part2(schematic: &Schematic) -> impl Display {
+        schematic.gear_ratio_sum()
+    }
+}
+
+pub const SOLVER: Solver = Solver::of::<Day3>();
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        Schematic::load(fs::read_to_string("samples/day3.txt").expect("Failed to read input"))
+            .unwrap()
+            .part_number_sum(),
+        4361
+    );
+}
+
+#[test]
+fn test_neighbours() {
+    let schematic = Schematic::load("467..114..\n...*......\n..35..633.".to_string()).unwrap();
+    let kinds = |part: &Part| {
+        schematic
+            .neighbour_parts(part)
+            .iter()
+            .map(|neighbour| format!("{:?}", neighbour.kind))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(kinds(&schematic.parts[0]), vec!["Symbol('*')"]);
+    assert!(kinds(&schematic.parts[1]).is_empty());
+    assert_eq!(
+        kinds(&schematic.parts[2]),
+        vec!["Number(467)", "Number(35)"]
+    );
+    assert_eq!(schematic.index.get((2, 2)), Some(&Some(3)));
+    assert_eq!(schematic.index.get((3, 2)), Some(&Some(3)));
+    assert_eq!(schematic.index.get((4, 2)), Some(&None));
+    assert_eq!(schematic.part_number_sum(), 467 + 35);
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(
+        Schematic::load(fs::read_to_string("samples/day3.txt").expect("Failed to read input"))
+            .unwrap()
+            .gear_ratio_sum(),
+        BigUint::from(467835u32)
+    );
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        Schematic::load("...*......................\n..123456789012345678901234".to_string())
+            .unwrap_err(),
+        ParseError::new(3, 2, 3, "part number up to 18446744073709551615")
+    );
+
+    // Numbers beyond 16 bits, with leading zeros taking up their own tiles
+    let schematic = Schematic::load("123456.007\n......*...".to_string()).unwrap();
+
+    assert_eq!(schematic.parts[0].kind(), &PartKind::Number(123456));
+    assert_eq!(schematic.parts[1].kind(), &PartKind::Number(7));
+    assert_eq!(schematic.parts[1].tiles(), &[(7, 0), (8, 0), (9, 0)]);
+    assert_eq!(schematic.index.get((9, 0)), Some(&Some(1)));
+    assert_eq!(schematic.part_number_sum(), 123456 + 7);
+}
+
+#[test]
+fn test_wrapped() {
+    let contents = "....12\n34*...\n......".to_string();
+    let schematic = Schematic::load(contents.clone()).unwrap();
+
+    // Only 34 touches the gear when rows end numbers
+    assert_eq!(schematic.parts[0].number(), Some(12));
+    assert_eq!(schematic.part_number_sum(), 34);
+
+    let schematic = Schematic::load_wrapped(contents).unwrap();
+
+    assert_eq!(schematic.parts[0].number(), Some(1234));
+    assert_eq!(
+        schematic.parts[0].tiles(),
+        &[(4, 0), (5, 0), (0, 1), (1, 1)]
+    );
+    assert_eq!(schematic.index.get((0, 1)), Some(&Some(0)));
+    assert_eq!(schematic.part_number_sum(), 1234);
+    assert_eq!(
+        schematic.gear_sum(&GearRule::new("*", 1..=1, Combine::Sum)),
+        BigUint::from(1234u32)
+    );
+
+    // Numbers can run over several rows, and the error points at where they start
+    let schematic = Schematic::load_wrapped("..1\n234\n5#.".to_string()).unwrap();
+    assert_eq!(schematic.parts[0].number(), Some(12345));
+    assert_eq!(
+        Schematic::load_wrapped("....1\n23456\n78901\n23456\n78901".to_string()).unwrap_err(),
+        ParseError::new(3, 1, 5, "part number up to 18446744073709551615")
+    );
+}
+
+#[test]
+fn test_overflow() {
+    // Each number fits a u64, but neither their product nor their sum does
+    let schematic = Schematic::load(
+        [
+            "99999999999*99999999999..................",
+            ".........................................",
+            "18446744073709551615+18446744073709551615",
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        schematic.gear_ratio_sum(),
+        BigUint::from(99999999999u64) * BigUint::from(99999999999u64)
+    );
+    assert_eq!(
+        schematic.part_number_sum(),
+        2 * 99999999999 + 2 * u64::MAX as u128
+    );
+    assert_eq!(
+        schematic.gear_sum(&GearRule::new("*+", 2..=4, Combine::Product)),
+        BigUint::from(99999999999u64).pow(2) + BigUint::from(u64::MAX).pow(2)
+    );
+}
+
+#[test]
+fn test_gear_rules() {
+    let schematic =
+        Schematic::load(fs::read_to_string("samples/day3.txt").expect("Failed to read input"))
+            .unwrap();
+    let gears = schematic.gears(&GearRule::ratio());
+
+    assert_eq!(gears.len(), 2);
+    assert_eq!(gears[0].symbol.pos(), (3, 1));
+    assert_eq!(
+        gears[0]
+            .parts
+            .iter()
+            .filter_map(|part| part.number())
+            .collect::<Vec<_>>(),
+        vec![467, 35]
+    );
+    assert_eq!(gears[1].value, BigUint::from(755u32 * 598));
+
+    // Any number of neighbours, and every symbol
+    assert_eq!(
+        schematic.gear_sum(&GearRule::new("*", 1..=8, Combine::Sum)),
+        BigUint::from(467u32 + 35 + 617 + 755 + 598)
+    );
+    assert_eq!(
+        schematic.gear_sum(&GearRule::new("*#+$", 1..=8, Combine::Max)),
+        BigUint::from(467u32 + 617 + 592 + 664 + 755 + 633)
+    );
+    assert_eq!(
+        schematic.gear_sum(&GearRule::new("*", 1..=1, Combine::Product)),
+        BigUint::from(617u32)
+    );
+    assert_eq!(
+        schematic
+            .gears(&GearRule::new("#", 1..=8, Combine::Sum))
+            .first()
+            .map(|gear| gear.symbol.kind()),
+        Some(&PartKind::Symbol('#'))
+    );
+}
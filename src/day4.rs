@@ -0,0 +1,535 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::{Report, Solution};
+use num::{BigUint, One, Zero};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+#[cfg(test)]
+use std::fs;
+
+// How the matches of one section of a card turn into points.
+// Doubling points outgrow a u32 from 33 matches on, so points are big integers like the copies.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Scoring {
+    // 1 point for the first match, doubled for every match after it
+    Doubling,
+    PerMatch(u32),
+}
+
+impl Scoring {
+    fn points(&self, matches: usize) -> BigUint {
+        match self {
+            Scoring::Doubling if matches == 0 => BigUint::zero(),
+            Scoring::Doubling => BigUint::one() << (matches - 1),
+            Scoring::PerMatch(points) => BigUint::from(*points) * matches,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScratchCard {
+    card_number: u32,
+    win_numbers: HashSet<u32>,
+    // Numbers on the card after each '|', usually just one section of them
+    draws: Vec<Vec<u32>>,
+}
+
+impl ScratchCard {
+    fn section_matches(&self, section: usize) -> Vec<&u32> {
+        self.draws[section]
+            .iter()
+            .filter(|number| self.win_numbers.contains(number))
+            .collect()
+    }
+
+    // Matching numbers of all sections
+    fn matches(&self) -> Vec<&u32> {
+        (0..self.draws.len())
+            .flat_map(|section| self.section_matches(section))
+            .collect()
+    }
+
+    // Card numbers beyond u32::MAX can't be on any card, so they aren't won either
+    fn won_card_numbers(&self) -> Vec<u32> {
+        (1..=self.matches().len() as u32)
+            .map_while(|offset| self.card_number.checked_add(offset))
+            .collect()
+    }
+
+    // Scores each section by the scoring at the same index, or by the last scoring for any sections beyond
+    fn points_with(&self, scoring: &[Scoring]) -> BigUint {
+        (0..self.draws.len())
+            .map(|section| {
+                scoring
+                    .get(section)
+                    .or(scoring.last())
+                    .map_or(BigUint::zero(), |scoring| {
+                        scoring.points(self.section_matches(section).len())
+                    })
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    cards: Vec<ScratchCard>,
+    scoring: Vec<Scoring>,
+}
+
+impl Puzzle {
+    // Copies held of every card once all won cards are in, in the same order as the cards.
+    // Cards only win cards with higher numbers, so one pass in card number order settles them all.
+    // Copies can double with every card, so they quickly outgrow any fixed width integer.
+    pub fn copies(&self) -> Vec<BigUint> {
+        let index: HashMap<u32, usize> = self
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(i, card)| (card.card_number, i))
+            .collect();
+        let mut order: Vec<usize> = (0..self.cards.len()).collect();
+        order.sort_by_key(|&i| self.cards[i].card_number);
+
+        let mut copies = vec![BigUint::one(); self.cards.len()];
+
+        for i in order {
+            for card_number in self.cards[i].won_card_numbers() {
+                if let Some(&won) = index.get(&card_number) {
+                    copies[won] = &copies[won] + &copies[i];
+                }
+            }
+        }
+
+        copies
+    }
+
+    // Won copies go by card number, so every card needs a number of its own
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        let mut card_numbers = HashSet::new();
+
+        Ok(Self {
+            cards: Line::all(Day4::DAY, &contents)
+                .map(|line| {
+                    let (lft, rgt) = line.split_once(":", "':' after the card number")?;
+                    let mut sections = rgt.split('|');
+                    let winning_numbers = sections.next().unwrap_or(rgt);
+                    let draws: Vec<&str> = sections.collect();
+                    let card_number = lft
+                        .split_ascii_whitespace()
+                        .last()
+                        .ok_or_else(|| line.error(lft, "card number"))?;
+
+                    if draws.is_empty() {
+                        return Err(line.error_at_end("'|' between the number lists"));
+                    }
+
+                    let number = line.parse::<u32>(card_number, "card number")?;
+
+                    if !card_numbers.insert(number) {
+                        return Err(line.error(card_number, "card number that isn't listed yet"));
+                    }
+
+                    Ok(ScratchCard {
+                        card_number: number,
+                        win_numbers: Self::parse_unique(&line, winning_numbers, "winning number")?
+                            .into_iter()
+                            .collect(),
+                        draws: draws
+                            .iter()
+                            .map(|draw| Self::parse_unique(&line, draw, "drawn number"))
+                            .collect::<Result<_, _>>()?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            scoring: vec![Scoring::Doubling],
+        })
+    }
+
+    // Numbers of one list, failing at the first number that is listed twice
+    fn parse_unique(line: &Line, list: &str, expected: &str) -> Result<Vec<u32>, ParseError> {
+        let mut seen = HashSet::new();
+
+        list.split_ascii_whitespace()
+            .map(|num| {
+                let number = line.parse::<u32>(num, expected)?;
+
+                if !seen.insert(number) {
+                    return Err(line.error(num, &format!("{} that isn't listed yet", expected)));
+                }
+
+                Ok(number)
+            })
+            .collect()
+    }
+
+    // Scoring of each section of the cards for part 1
+    pub fn with_scoring(mut self, scoring: &[Scoring]) -> Self {
+        self.scoring = scoring.to_vec();
+        self
+    }
+
+    // How every card adds to the pile of cards
+    pub fn explain(&self) -> CascadeReport {
+        let copies = self.copies();
+        let card_numbers: HashSet<u32> = self.cards.iter().map(|card| card.card_number).collect();
+        let won: Vec<Vec<u32>> = self
+            .cards
+            .iter()
+            .map(|card| {
+                card.won_card_numbers()
+                    .into_iter()
+                    .filter(|number| card_numbers.contains(number))
+                    .collect()
+            })
+            .collect();
+
+        CascadeReport {
+            cards: self
+                .cards
+                .iter()
+                .enumerate()
+                .map(|(i, card)| CardBreakdown {
+                    card_number: card.card_number,
+                    matches: card.matches().into_iter().copied().collect(),
+                    points: card.points_with(&self.scoring),
+                    won: won[i].clone(),
+                    copies: copies[i].clone(),
+                    won_from: self
+                        .cards
+                        .iter()
+                        .zip(&won)
+                        .filter(|(_, won)| won.contains(&card.card_number))
+                        .map(|(other, _)| other.card_number)
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn pt1(&self) -> BigUint {
+        self.cards
+            .iter()
+            .map(|card| card.points_with(&self.scoring))
+            .sum()
+    }
+
+    pub fn pt2(&self) -> BigUint {
+        self.copies().iter().sum()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CardBreakdown {
+    pub card_number: u32,
+    pub matches: Vec<u32>,
+    pub points: BigUint,
+    // Cards this card wins a copy of, for each copy of it
+    pub won: Vec<u32>,
+    pub copies: BigUint,
+    // Cards that won copies of this card
+    pub won_from: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CascadeReport {
+    pub cards: Vec<CardBreakdown>,
+}
+
+impl CascadeReport {
+    pub fn table(&self) -> String {
+        let list = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|number| number.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        let width = self
+            .cards
+            .iter()
+            .map(|card| list(&card.won).len())
+            .max()
+            .unwrap_or(0)
+            .max("won".len());
+
+        let mut table = format!(
+            "{:>5}  {:>7}  {:>6}  {:>6}  {:<width$}  won from",
+            "card", "matches", "points", "copies", "won"
+        );
+        self.cards.iter().for_each(|card| {
+            let row = format!(
+                "{:>5}  {:>7}  {:>6}  {:>6}  {:<width$}  {}",
+                card.card_number,
+                card.matches.len(),
+                card.points,
+                card.copies,
+                list(&card.won),
+                list(&card.won_from)
+            );
+            table += "\n";
+            table += row.trim_end();
+        });
+
+        table
+    }
+
+    // Graphviz graph with an edge from every card to each card it wins, labelled by the copies it adds
+    pub fn dot(&self) -> String {
+        let mut dot = "digraph cascade {".to_string();
+        self.cards.iter().for_each(|card| {
+            dot += &format!(
+                "\n    card{0} [label=\"Card {0}\\n{1} matches, {2} copies\"];",
+                card.card_number,
+                card.matches.len(),
+                card.copies
+            );
+        });
+        self.cards.iter().for_each(|card| {
+            card.won.iter().for_each(|won| {
+                dot += &format!(
+                    "\n    card{} -> card{} [label=\"+{}\"];",
+                    card.card_number, won, card.copies
+                );
+            });
+        });
+        dot += "\n}";
+
+        dot
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Puzzle;
+
+    fn parse(contents: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::load(contents.to_string())
+    }
+
+    fn part1(puzzle: &Puzzle) -> impl Display {
+        puzzle.pt1()
+    }
+
+    fn part2(puzzle: &Puzzle) -> impl Display {
+        puzzle.pt2()
+    }
+}
+
+impl Report for Day4 {
+    const FORMATS: &'static [&'static str] = &["table", "dot"];
+
+    // The cascade only plays a part in part 2, but it also shows the points of part 1
+    fn report(puzzle: &Puzzle, _part: u8, format: &str) -> Result<String, ParseError> {
+        Ok(match format {
+            "dot" => puzzle.explain().dot(),
+            _ => puzzle.explain().table(),
+        })
+    }
+}
+
+pub const SOLVER: Solver = Solver::reporting::<Day4>();
+
+#[test]
+fn test_common() {
+    assert_eq!(
+        ScratchCard {
+            card_number: 1,
+            win_numbers: HashSet::from([1, 2, 3, 4, 5, 6, 7]),
+            draws: vec![vec![2, 4, 5, 9]]
+        }
+        .points_with(&[Scoring::Doubling]),
+        BigUint::from(4u32)
+    );
+
+    assert_eq!(
+        ScratchCard {
+            card_number: 10,
+            win_numbers: HashSet::from([1, 2, 3, 4, 5, 6, 7]),
+            draws: vec![vec![1, 2, 3, 4, 5]]
+        }
+        .won_card_numbers(),
+        vec![11, 12, 13, 14, 15]
+    );
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        Puzzle::load(fs::read_to_string("samples/day4.txt").expect("Failed to read input"))
+            .unwrap()
+            .pt1(),
+        BigUint::from(13u32)
+    );
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(
+        Puzzle::load(fs::read_to_string("samples/day4.txt").expect("Failed to read input"))
+            .unwrap()
+            .pt2(),
+        BigUint::from(30u32)
+    );
+}
+
+#[test]
+fn test_copies() {
+    let puzzle =
+        Puzzle::load(fs::read_to_string("samples/day4.txt").expect("Failed to read input"))
+            .unwrap();
+
+    assert_eq!(
+        puzzle.copies(),
+        [1u32, 2, 4, 8, 14, 1].map(BigUint::from).to_vec()
+    );
+
+    // Every card wins all cards after it, doubling the copies down the line beyond a u128
+    let contents = (1..=130)
+        .map(|number| {
+            let numbers = (1..=130 - number)
+                .map(|num| num.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("Card {}: {} | {}", number, numbers, numbers)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let puzzle = Puzzle::load(contents).unwrap();
+
+    assert_eq!(puzzle.copies()[0], BigUint::one());
+    assert_eq!(puzzle.copies()[129], BigUint::one() << 129);
+    assert_eq!(puzzle.pt2(), (BigUint::one() << 130) - 1u32);
+
+    // Cards out of order and cards won past the last card
+    let puzzle = Puzzle::load("Card 2: 5 | 5\nCard 1: 7 8 | 7 8".to_string()).unwrap();
+
+    assert_eq!(puzzle.copies(), [2u32, 1].map(BigUint::from).to_vec());
+    assert_eq!(puzzle.pt2(), BigUint::from(3u32));
+
+    // Card numbers run out at u32::MAX
+    let puzzle =
+        Puzzle::load("Card 4294967294: 1 2 3 | 1 2 3\nCard 4294967295: 4 | 4".to_string()).unwrap();
+
+    assert_eq!(puzzle.cards[0].won_card_numbers(), vec![u32::MAX]);
+    assert!(puzzle.cards[1].won_card_numbers().is_empty());
+    assert_eq!(puzzle.pt2(), BigUint::from(3u32));
+}
+
+#[test]
+fn test_explain() {
+    let report =
+        Puzzle::load(fs::read_to_string("samples/day4.txt").expect("Failed to read input"))
+            .unwrap()
+            .explain();
+
+    assert_eq!(
+        report.cards[2],
+        CardBreakdown {
+            card_number: 3,
+            matches: vec![21, 1],
+            points: BigUint::from(2u32),
+            won: vec![4, 5],
+            copies: BigUint::from(4u32),
+            won_from: vec![1, 2],
+        }
+    );
+    assert_eq!(
+        report.table(),
+        [
+            " card  matches  points  copies  won      won from",
+            "    1        4       8       1  2,3,4,5",
+            "    2        2       2       2  3,4      1",
+            "    3        2       2       4  4,5      1,2",
+            "    4        1       1       8  5        1,2,3",
+            "    5        0       0      14           1,3,4",
+            "    6        0       0       1",
+        ]
+        .join("\n")
+    );
+
+    let dot = report.dot();
+
+    assert!(
+        dot.starts_with("digraph cascade {\n    card1 [label=\"Card 1\\n4 matches, 1 copies\"];")
+    );
+    assert!(dot.contains("\n    card3 -> card5 [label=\"+4\"];"));
+    assert_eq!(dot.matches("->").count(), 4 + 2 + 2 + 1);
+    assert!(dot.ends_with('}'));
+}
+
+#[test]
+fn test_sections() {
+    let puzzle =
+        Puzzle::load("Card 1: 41 48 83|83 86 6 48|17 48 9\nCard 2: 1 2|3|4".to_string()).unwrap();
+
+    assert_eq!(puzzle.cards[0].draws.len(), 2);
+    assert_eq!(puzzle.cards[0].matches(), vec![&83, &48, &48]);
+    assert_eq!(puzzle.cards[0].won_card_numbers(), vec![2, 3, 4]);
+    assert_eq!(
+        puzzle.cards[0].points_with(&[Scoring::Doubling]),
+        BigUint::from(2u32 + 1)
+    );
+    assert_eq!(puzzle.pt1(), BigUint::from(3u32));
+    assert_eq!(puzzle.copies(), [1u32, 2].map(BigUint::from).to_vec());
+
+    let puzzle = puzzle.with_scoring(&[Scoring::PerMatch(10), Scoring::Doubling]);
+
+    assert_eq!(puzzle.pt1(), BigUint::from(20u32 + 1));
+    assert_eq!(puzzle.explain().cards[0].points, BigUint::from(21u32));
+
+    // The sample scores the same both ways, apart from the scoring
+    let sample = fs::read_to_string("samples/day4.txt").expect("Failed to read input");
+
+    assert_eq!(
+        Puzzle::load(sample.clone())
+            .unwrap()
+            .with_scoring(&[Scoring::PerMatch(1)])
+            .pt1(),
+        BigUint::from(4u32 + 2 + 2 + 1)
+    );
+
+    // Points outgrow a u32 with enough matches
+    let numbers = (1..=40)
+        .map(|num| num.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let puzzle = Puzzle::load(format!("Card 1: {} | {}", numbers, numbers)).unwrap();
+
+    assert_eq!(puzzle.pt1(), BigUint::one() << 39);
+    assert_eq!(
+        puzzle.with_scoring(&[Scoring::PerMatch(u32::MAX)]).pt1(),
+        BigUint::from(u32::MAX) * 40u32
+    );
+    assert_eq!(
+        Puzzle::load(sample.replace(" | ", "|")).unwrap().pt2(),
+        BigUint::from(30u32)
+    );
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        Puzzle::load("Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 3O".to_string()).unwrap_err(),
+        ParseError::new(4, 2, 20, "drawn number")
+    );
+    assert_eq!(
+        Puzzle::load("Card 1: 41 48 83 86".to_string()).unwrap_err(),
+        ParseError::new(4, 1, 20, "'|' between the number lists")
+    );
+    assert_eq!(
+        Puzzle::load("Card 1: 41 48 | 83 86\nCard 2: 13 32 13 | 61 30".to_string()).unwrap_err(),
+        ParseError::new(4, 2, 15, "winning number that isn't listed yet")
+    );
+    assert_eq!(
+        Puzzle::load("Card 1: 41 48 | 83 86 | 17 83 17".to_string()).unwrap_err(),
+        ParseError::new(4, 1, 31, "drawn number that isn't listed yet")
+    );
+    assert_eq!(
+        Puzzle::load("Card 1: 41 | 41\nCard 2: 13 | 61\nCard  1: 7 | 8".to_string()).unwrap_err(),
+        ParseError::new(4, 3, 7, "card number that isn't listed yet")
+    );
+}
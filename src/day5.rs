@@ -0,0 +1,789 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::fmt::{Display, Formatter};
+#[cfg(test)]
+use std::fs;
+use std::ops::Range;
+
+#[derive(Debug)]
+pub struct Map {
+    source: String,
+    dest: String,
+    entries: Vec<MapEntry>,
+}
+
+impl Map {
+    pub fn name(&self) -> String {
+        format!("{}-to-{}", self.source, self.dest)
+    }
+
+    pub fn convert(&self, from: u64) -> u64 {
+        match self
+            .entries
+            .iter()
+            .find(|map_entry| map_entry.is_applicable(from))
+        {
+            Some(map_entry) => map_entry.convert(from),
+            None => from,
+        }
+    }
+
+    // Converts whole ranges at once, splitting them where they overlap different entries
+    pub fn convert_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut converted = vec![];
+        let mut remaining = ranges.to_vec();
+
+        // Like convert(), the first applicable entry wins, so later entries only see what's left
+        for map_entry in &self.entries {
+            remaining = remaining
+                .iter()
+                .flat_map(|range| {
+                    let (range_converted, range_remaining) = map_entry.convert_range(range);
+                    converted.extend(range_converted);
+                    range_remaining
+                })
+                .collect();
+        }
+
+        // Ranges not covered by any entry map to themselves
+        converted.extend(remaining);
+        converted
+    }
+
+    // All values that convert to the given value (more than one if the map isn't injective)
+    pub fn invert(&self, to: u64) -> Vec<u64> {
+        let mut sources: Vec<u64> = self
+            .entries
+            .iter()
+            .filter_map(|map_entry| map_entry.invert(to))
+            .chain([to])
+            // Entries can be shadowed by earlier entries, and values covered by an entry don't map to themselves
+            .filter(|&from| self.convert(from) == to)
+            .collect();
+
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    // Sources in between the entries, which map to themselves
+    pub fn gaps(&self) -> Vec<Range<u64>> {
+        let mut covered: Vec<Range<u128>> = self
+            .entries
+            .iter()
+            .map(|map_entry| map_entry.source_range())
+            .filter(|range| !range.is_empty())
+            .collect();
+        covered.sort_by_key(|range| range.start);
+
+        covered
+            .windows(2)
+            .scan(0, |end, pair| {
+                *end = pair[0].end.max(*end);
+                Some(*end..pair[1].start)
+            })
+            .filter(|gap| !gap.is_empty())
+            .map(|gap| gap.start as u64..gap.end as u64)
+            .collect()
+    }
+
+    fn validate(&self, issues: &mut Vec<Issue>) {
+        // Entries are numbered from 1 in the order they are listed
+        let entries: Vec<(usize, &MapEntry)> = (1..).zip(&self.entries).collect();
+
+        for &(entry, map_entry) in &entries {
+            if map_entry
+                .source_start
+                .checked_add(map_entry.range)
+                .is_none()
+                || map_entry.dest_start.checked_add(map_entry.range).is_none()
+            {
+                issues.push(Issue::Overflow {
+                    map: self.name(),
+                    entry,
+                });
+            }
+        }
+
+        for (i, &(first, first_entry)) in entries.iter().enumerate() {
+            for &(second, second_entry) in &entries[i + 1..] {
+                if let Some(overlap) =
+                    overlap(first_entry.source_range(), second_entry.source_range())
+                {
+                    issues.push(Issue::SourceOverlap {
+                        map: self.name(),
+                        entries: (first, second),
+                        overlap,
+                    });
+                }
+
+                if let Some(overlap) = overlap(first_entry.dest_range(), second_entry.dest_range())
+                {
+                    issues.push(Issue::DestOverlap {
+                        map: self.name(),
+                        entries: (first, second),
+                        overlap,
+                    });
+                }
+            }
+        }
+    }
+}
+
+// Common part of two ranges, if any, cut off at u64::MAX
+fn overlap(a: Range<u128>, b: Range<u128>) -> Option<Range<u64>> {
+    let start = a.start.max(b.start);
+    let end = a.end.min(b.end).min(u64::MAX as u128);
+
+    (start < end).then_some(start as u64..end as u64)
+}
+
+// Inconsistencies in an almanac, where entries are numbered from 1 within their map
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Issue {
+    // Values the second entry never converts, as convert() uses the first entry that applies
+    SourceOverlap {
+        map: String,
+        entries: (usize, usize),
+        overlap: Range<u64>,
+    },
+    // Values that more than one source converts to, so the map isn't injective
+    DestOverlap {
+        map: String,
+        entries: (usize, usize),
+        overlap: Range<u64>,
+    },
+    // Source or destination range ending beyond u64::MAX
+    Overflow {
+        map: String,
+        entry: usize,
+    },
+    // Map that doesn't start where the map before it ends (or at seed for the first one)
+    ChainMismatch {
+        map: String,
+        expected: String,
+    },
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::SourceOverlap {
+                map,
+                entries: (first, second),
+                overlap,
+            } => write!(
+                f,
+                "{}: entries {} and {} both convert {:?}, entry {} is ignored there",
+                map, first, second, overlap, second
+            ),
+            Issue::DestOverlap {
+                map,
+                entries: (first, second),
+                overlap,
+            } => write!(
+                f,
+                "{}: entries {} and {} both convert to {:?}",
+                map, first, second, overlap
+            ),
+            Issue::Overflow { map, entry } => {
+                write!(f, "{}: entry {} overflows u64", map, entry)
+            }
+            Issue::ChainMismatch { map, expected } => {
+                write!(f, "{}: expected a map from {}", map, expected)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MapEntry {
+    source_start: u64,
+    dest_start: u64,
+    range: u64,
+}
+
+impl MapEntry {
+    // Ranges as u128, so their ends can't overflow
+    fn source_range(&self) -> Range<u128> {
+        self.source_start as u128..self.source_start as u128 + self.range as u128
+    }
+
+    fn dest_range(&self) -> Range<u128> {
+        self.dest_start as u128..self.dest_start as u128 + self.range as u128
+    }
+
+    // Length of the entry as far as both of its ranges stay below u64::MAX, so conversions can't overflow.
+    // validate() reports entries that get cut short this way.
+    fn len(&self) -> u64 {
+        self.range
+            .min(u64::MAX - self.source_start)
+            .min(u64::MAX - self.dest_start)
+    }
+
+    pub fn is_applicable(&self, from: u64) -> bool {
+        from >= self.source_start && from - self.source_start < self.len()
+    }
+
+    pub fn convert(&self, from: u64) -> u64 {
+        if !self.is_applicable(from) {
+            return from;
+        }
+
+        self.dest_start + (from - self.source_start)
+    }
+
+    pub fn invert(&self, to: u64) -> Option<u64> {
+        if to < self.dest_start || to - self.dest_start >= self.len() {
+            return None;
+        }
+
+        Some(self.source_start + (to - self.dest_start))
+    }
+
+    // Returns the converted part of the range that this entry applies to, and the parts it doesn't
+    pub fn convert_range(&self, from: &Range<u64>) -> (Option<Range<u64>>, Vec<Range<u64>>) {
+        let start = from.start.max(self.source_start);
+        let end = from.end.min(self.source_start + self.len());
+
+        if start >= end {
+            return (None, vec![from.clone()]);
+        }
+
+        (
+            Some(self.convert(start)..self.convert(start) + (end - start)),
+            [from.start..start, end..from.end]
+                .into_iter()
+                .filter(|range| !range.is_empty())
+                .collect(),
+        )
+    }
+}
+
+// Chain of maps where each map converts to the source of the next one
+#[derive(Debug)]
+pub struct Pipeline<'a> {
+    maps: Vec<&'a Map>,
+}
+
+impl Pipeline<'_> {
+    pub fn convert(&self, from: u64) -> u64 {
+        self.maps.iter().fold(from, |acc, map| map.convert(acc))
+    }
+
+    pub fn convert_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        self.maps
+            .iter()
+            .fold(ranges.to_vec(), |acc, map| map.convert_ranges(&acc))
+    }
+
+    pub fn invert(&self, to: u64) -> Vec<u64> {
+        let mut sources = self.maps.iter().rev().fold(vec![to], |acc, map| {
+            acc.iter().flat_map(|&value| map.invert(value)).collect()
+        });
+
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    // Part 2 reads the seeds as pairs of start and length, which part 1 doesn't need to make sense
+    seed_ranges: Result<Vec<Range<u64>>, ParseError>,
+    maps: Vec<Map>,
+}
+
+impl Almanac {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        let mut lines = Line::all(Day5::DAY, &contents);
+        let seeds_line = lines
+            .next()
+            .ok_or_else(|| ParseError::new(Day5::DAY, 1, 1, "'seeds: ' line"))?;
+        let tokens: Vec<&str> = seeds_line
+            .text
+            .strip_prefix("seeds: ")
+            .ok_or_else(|| seeds_line.error(seeds_line.text, "'seeds: '"))?
+            .split_ascii_whitespace()
+            .collect();
+        let seeds: Vec<u64> = tokens
+            .iter()
+            .map(|seed| seeds_line.parse::<u64>(seed, "seed number"))
+            .collect::<Result<_, _>>()?;
+
+        if seeds.is_empty() {
+            return Err(seeds_line.error_at_end("seed number"));
+        }
+
+        let seed_ranges = match seeds.len() % 2 {
+            1 => Err(seeds_line.error_at_end("seed range length")),
+            _ => seeds
+                .chunks(2)
+                .zip(tokens.chunks(2))
+                .map(|(pair, pair_tokens)| {
+                    let end = pair[0].checked_add(pair[1]).ok_or_else(|| {
+                        seeds_line.error(
+                            pair_tokens[1],
+                            &format!("seed range length up to {}", u64::MAX - pair[0]),
+                        )
+                    })?;
+
+                    Ok(pair[0]..end)
+                })
+                .collect(),
+        };
+        let mut maps: Vec<Map> = vec![];
+
+        for line in lines.filter(|line| !line.text.is_empty()) {
+            if let Some(name) = line.text.strip_suffix(" map:") {
+                let (source, dest) = name
+                    .split_once("-to-")
+                    .ok_or_else(|| line.error(name, "'<source>-to-<destination>' map name"))?;
+
+                maps.push(Map {
+                    source: source.to_string(),
+                    dest: dest.to_string(),
+                    entries: vec![],
+                });
+                continue;
+            }
+
+            let map = maps
+                .last_mut()
+                .ok_or_else(|| line.error(line.text, "map header"))?;
+            let nums: Vec<u64> = line
+                .text
+                .split_ascii_whitespace()
+                .map(|num| line.parse::<u64>(num, "range number"))
+                .collect::<Result<_, _>>()?;
+
+            if nums.len() != 3 {
+                return Err(line.error_at_end("three range numbers"));
+            }
+
+            map.entries.push(MapEntry {
+                dest_start: nums[0],
+                source_start: nums[1],
+                range: nums[2],
+            });
+        }
+
+        Ok(Self {
+            seeds,
+            seed_ranges,
+            maps,
+        })
+    }
+
+    // Everything that makes the maps convert differently from how they read, empty if all is well
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = vec![];
+        let mut expected = "seed";
+
+        for map in &self.maps {
+            if map.source != expected {
+                issues.push(Issue::ChainMismatch {
+                    map: map.name(),
+                    expected: expected.to_string(),
+                });
+            }
+            expected = &map.dest;
+
+            map.validate(&mut issues);
+        }
+
+        issues
+    }
+
+    pub fn map(&self, name: &str) -> Option<&Map> {
+        self.maps.iter().find(|map| map.name() == name)
+    }
+
+    // Follows the maps from one category to another, e.g. seed to humidity
+    pub fn pipeline(&self, source: &str, dest: &str) -> Option<Pipeline<'_>> {
+        let mut maps = vec![];
+        let mut current = source;
+
+        while current != dest {
+            // Every map can be used at most once, which also ends cyclic chains
+            if maps.len() == self.maps.len() {
+                return None;
+            }
+
+            let map = self.maps.iter().find(|map| map.source == current)?;
+            current = &map.dest;
+            maps.push(map);
+        }
+
+        Some(Pipeline { maps })
+    }
+
+    // All seeds that end up at the given location
+    pub fn from_location(&self, location: u64) -> Vec<u64> {
+        match self.pipeline("seed", "location") {
+            Some(pipeline) => pipeline.invert(location),
+            None => vec![],
+        }
+    }
+
+    pub fn to_location(&self, seed: &u64) -> u64 {
+        self.maps.iter().fold(*seed, |acc, map| map.convert(acc))
+    }
+
+    pub fn to_location_ranges(&self, seeds: &[Range<u64>]) -> Vec<Range<u64>> {
+        self.maps
+            .iter()
+            .fold(seeds.to_vec(), |acc, map| map.convert_ranges(&acc))
+    }
+
+    pub fn pt1(&self) -> u64 {
+        self.seeds
+            .iter()
+            .map(|seed| self.to_location(seed))
+            .min()
+            .unwrap()
+    }
+
+    pub fn pt2(&self) -> Result<u64, ParseError> {
+        let seed_ranges = self.seed_ranges.as_ref().map_err(Clone::clone)?;
+
+        // 1815746760 seeds o_O -> convert the ranges as a whole instead of seed by seed
+        Ok(self
+            .to_location_ranges(seed_ranges)
+            .iter()
+            .map(|range| range.start)
+            .min()
+            .unwrap())
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+
+    fn parse(contents: &str) -> Result<Almanac, ParseError> {
+        Almanac::load(contents.to_string())
+    }
+
+    fn part1(almanac: &Almanac) -> impl Display {
+        almanac.pt1()
+    }
+
+    // Panics on seeds that don't pair up into ranges, which try_part2 reports instead
+    fn part2(almanac: &Almanac) -> impl Display {
+        almanac.pt2().unwrap()
+    }
+
+    fn try_part2(almanac: &Almanac) -> Result<String, ParseError> {
+        almanac.pt2().map(|location| location.to_string())
+    }
+}
+
+pub const SOLVER: Solver = Solver::of::<Day5>();
+
+#[test]
+fn test_common() {
+    assert_eq!(
+        MapEntry {
+            dest_start: 52,
+            source_start: 50,
+            range: 48
+        }
+        .convert(53),
+        55
+    );
+    assert_eq!(
+        MapEntry {
+            dest_start: 52,
+            source_start: 50,
+            range: 48
+        }
+        .convert(10),
+        10
+    );
+    assert_eq!(
+        MapEntry {
+            dest_start: 52,
+            source_start: 50,
+            range: 48
+        }
+        .convert(97),
+        99
+    );
+    assert_eq!(
+        MapEntry {
+            dest_start: 52,
+            source_start: 50,
+            range: 48
+        }
+        .convert(98),
+        98
+    );
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_ranges() {
+    let map_entry = MapEntry {
+        dest_start: 52,
+        source_start: 50,
+        range: 48,
+    };
+
+    assert_eq!(map_entry.convert_range(&(10..20)), (None, vec![10..20]));
+    assert_eq!(map_entry.convert_range(&(60..70)), (Some(62..72), vec![]));
+    assert_eq!(
+        map_entry.convert_range(&(40..100)),
+        (Some(52..100), vec![40..50, 98..100])
+    );
+
+    let map = Map {
+        source: "seed".to_string(),
+        dest: "soil".to_string(),
+        entries: vec![
+            MapEntry {
+                dest_start: 50,
+                source_start: 98,
+                range: 2,
+            },
+            map_entry,
+        ],
+    };
+
+    assert_eq!(
+        map.convert_ranges(&[45..100]),
+        vec![50..52, 52..100, 45..50]
+    );
+}
+
+#[test]
+fn test_pipeline() {
+    let almanac =
+        Almanac::load(fs::read_to_string("samples/day5.txt").expect("Failed to read input"))
+            .unwrap();
+
+    assert_eq!(almanac.map("seed-to-soil").unwrap().convert(79), 81);
+    assert_eq!(almanac.map("light-to-temperature").unwrap().convert(74), 78);
+    assert!(almanac.map("seed-to-location").is_none());
+
+    assert_eq!(
+        almanac.pipeline("seed", "humidity").unwrap().convert(79),
+        78
+    );
+    assert_eq!(almanac.pipeline("water", "light").unwrap().convert(81), 74);
+    assert_eq!(almanac.pipeline("seed", "seed").unwrap().convert(79), 79);
+    assert!(almanac.pipeline("soil", "seed").is_none());
+
+    assert_eq!(almanac.map("seed-to-soil").unwrap().invert(81), vec![79]);
+    assert_eq!(almanac.map("seed-to-soil").unwrap().invert(50), vec![98]);
+    assert_eq!(almanac.map("seed-to-soil").unwrap().invert(10), vec![10]);
+    assert_eq!(
+        almanac.pipeline("seed", "humidity").unwrap().invert(78),
+        vec![79]
+    );
+    assert_eq!(almanac.from_location(82), vec![79]);
+
+    let merging_map = Map {
+        source: "a".to_string(),
+        dest: "b".to_string(),
+        entries: vec![
+            MapEntry {
+                dest_start: 0,
+                source_start: 10,
+                range: 5,
+            },
+            MapEntry {
+                dest_start: 0,
+                source_start: 20,
+                range: 5,
+            },
+        ],
+    };
+    assert_eq!(merging_map.name(), "a-to-b");
+    assert_eq!(merging_map.invert(2), vec![2, 12, 22]);
+    assert_eq!(merging_map.invert(12), vec![]);
+
+    // The sample maps are bijective: every location leads back to exactly the seed it came from
+    (0..100).for_each(|seed| {
+        assert_eq!(
+            almanac.from_location(almanac.to_location(&seed)),
+            vec![seed]
+        )
+    });
+}
+
+#[test]
+fn test_validate() {
+    let almanac =
+        Almanac::load(fs::read_to_string("samples/day5.txt").expect("Failed to read input"))
+            .unwrap();
+
+    assert_eq!(almanac.validate(), vec![]);
+    assert_eq!(almanac.map("seed-to-soil").unwrap().gaps(), vec![]);
+    assert_eq!(almanac.map("water-to-light").unwrap().gaps(), vec![]);
+    assert_eq!(almanac.map("fertilizer-to-water").unwrap().gaps(), vec![]);
+
+    let almanac = Almanac::load(
+        "seeds: 1 1
+
+seed-to-soil map:
+50 98 5
+52 50 50
+0 200 10
+
+fertilizer-to-water map:
+0 18446744073709551610 10"
+            .to_string(),
+    )
+    .unwrap();
+    let issues = almanac.validate();
+
+    assert_eq!(
+        issues,
+        vec![
+            Issue::SourceOverlap {
+                map: "seed-to-soil".to_string(),
+                entries: (1, 2),
+                overlap: 98..100
+            },
+            Issue::DestOverlap {
+                map: "seed-to-soil".to_string(),
+                entries: (1, 2),
+                overlap: 52..55
+            },
+            Issue::ChainMismatch {
+                map: "fertilizer-to-water".to_string(),
+                expected: "soil".to_string()
+            },
+            Issue::Overflow {
+                map: "fertilizer-to-water".to_string(),
+                entry: 1
+            },
+        ]
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "seed-to-soil: entries 1 and 2 both convert 98..100, entry 2 is ignored there"
+    );
+    assert_eq!(almanac.map("seed-to-soil").unwrap().gaps(), vec![103..200]);
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_overflowing_entries() {
+    let almanac = Almanac::load(
+        "seeds: 18446744073709551612 1
+
+seed-to-soil map:
+0 18446744073709551610 10
+18446744073709551613 5 10"
+            .to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        almanac.validate(),
+        vec![
+            Issue::Overflow {
+                map: "seed-to-soil".to_string(),
+                entry: 1
+            },
+            Issue::Overflow {
+                map: "seed-to-soil".to_string(),
+                entry: 2
+            }
+        ]
+    );
+
+    // Entries convert up to u64::MAX on either side and leave everything past it alone
+    assert_eq!(almanac.to_location(&18446744073709551612), 2);
+    assert_eq!(almanac.pt1(), 1);
+    assert_eq!(almanac.pt2(), Ok(2));
+    assert_eq!(almanac.to_location(&u64::MAX), u64::MAX);
+    assert_eq!(almanac.to_location(&6), 18446744073709551614);
+    assert_eq!(almanac.to_location(&7), 7);
+    assert_eq!(
+        almanac.to_location_ranges(&[0..u64::MAX]),
+        vec![
+            0..5,
+            18446744073709551613..u64::MAX,
+            0..5,
+            7..18446744073709551610
+        ]
+    );
+
+    let map = almanac.map("seed-to-soil").unwrap();
+    assert_eq!(map.invert(4), vec![4, 18446744073709551614]);
+    assert_eq!(map.invert(18446744073709551614), vec![6]);
+}
+
+#[test]
+fn test_part1() {
+    let almanac =
+        Almanac::load(fs::read_to_string("samples/day5.txt").expect("Failed to read input"))
+            .unwrap();
+
+    assert_eq!(almanac.to_location(&79), 82);
+    assert_eq!(almanac.to_location(&14), 43);
+    assert_eq!(almanac.to_location(&55), 86);
+    assert_eq!(almanac.to_location(&13), 35);
+    assert_eq!(almanac.pt1(), 35);
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_part2() {
+    let almanac =
+        Almanac::load(fs::read_to_string("samples/day5.txt").expect("Failed to read input"))
+            .unwrap();
+
+    assert_eq!(almanac.to_location_ranges(&[82..83]), vec![46..47]);
+    assert_eq!(almanac.pt2(), Ok(46));
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_parse() {
+    assert_eq!(
+        Almanac::load("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50".to_string()).unwrap_err(),
+        ParseError::new(5, 5, 6, "three range numbers")
+    );
+    assert_eq!(
+        Almanac::load("seeds: 79 1a\n".to_string()).unwrap_err(),
+        ParseError::new(5, 1, 11, "seed number")
+    );
+    assert_eq!(
+        (SOLVER.part2)("seeds: 1 2 3\n"),
+        Err(ParseError::new(5, 1, 13, "seed range length"))
+    );
+    assert_eq!(
+        Almanac::load("seeds: \n".to_string()).unwrap_err(),
+        ParseError::new(5, 1, 8, "seed number")
+    );
+    assert_eq!(
+        (SOLVER.part2)("seeds: 18446744073709551610 6"),
+        Err(ParseError::new(5, 1, 29, "seed range length up to 5"))
+    );
+    assert_eq!(
+        Almanac::load("seeds: 18446744073709551610 5".to_string())
+            .unwrap()
+            .seed_ranges,
+        Ok(vec![18446744073709551610..u64::MAX])
+    );
+
+    // Part 1 takes the seeds one by one, however many there are
+    assert_eq!(
+        (SOLVER.part1)("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2"),
+        Ok("14".to_string())
+    );
+    assert_eq!(
+        (SOLVER.part1)("seeds: 18446744073709551610 6"),
+        Ok("6".to_string())
+    );
+}
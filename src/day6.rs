@@ -0,0 +1,672 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::{Report, Solution};
+use num::{BigUint, One};
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+#[derive(Debug)]
+pub struct Race {
+    time: u128,
+    dist: u128,
+}
+
+impl Race {
+    pub fn new(time: u128, dist: u128) -> Self {
+        Self { time, dist }
+    }
+
+    // Distance travelled when holding the button for the given time,
+    // None when the hold outlasts the race or the distance overflows a u128
+    pub fn distance(&self, hold: u128) -> Option<u128> {
+        hold.checked_mul(self.time.checked_sub(hold)?)
+    }
+
+    // Whether holding the button for the given time travels further than the record
+    fn beats_record(&self, hold: u128) -> bool {
+        // Overflowing distances are beyond any record
+        hold <= self.time && self.distance(hold).is_none_or(|dist| dist > self.dist)
+    }
+
+    // How much further than the record the given hold time travels, saturating when the distance overflows
+    pub fn margin(&self, hold: u128) -> Option<u128> {
+        if hold > self.time {
+            return None;
+        }
+
+        match self.distance(hold) {
+            Some(dist) => dist.checked_sub(self.dist).filter(|&margin| margin > 0),
+            None => Some(u128::MAX - self.dist),
+        }
+    }
+
+    // The distance h*(t-h) peaks at h=t/2 and is symmetric around it
+    pub fn best_hold(&self) -> u128 {
+        self.time / 2
+    }
+
+    pub fn max_distance(&self) -> Option<u128> {
+        self.distance(self.best_hold())
+    }
+
+    // Lowest record nobody can beat, None when even u128::MAX can be beaten
+    pub fn unwinnable_record(&self) -> Option<u128> {
+        self.max_distance()
+    }
+
+    pub fn ways_to_win(&self) -> u128 {
+        self.winning_holds()
+            .map_or(0, |holds| holds.end() - holds.start() + 1)
+    }
+
+    pub fn winning_holds(&self) -> Option<RangeInclusive<u128>> {
+        if !self.beats_record(self.best_hold()) {
+            return None;
+        }
+
+        // Shortest winning hold from the roots h=(1/2)(t +- sqrt(t^2-4s)), when t^2 fits in a u128
+        let mut shortest = match self
+            .time
+            .checked_mul(self.time)
+            .zip(self.dist.checked_mul(4))
+            .and_then(|(time_sq, dist)| time_sq.checked_sub(dist))
+        {
+            Some(disc) => (self.time - isqrt(disc)) / 2,
+            None => self.shortest_hold_search(),
+        };
+
+        // The integer root may be off by one in either direction
+        while shortest > 0 && self.beats_record(shortest - 1) {
+            shortest -= 1;
+        }
+        while !self.beats_record(shortest) {
+            shortest += 1;
+        }
+
+        // Winning holds range from the shortest one up to its mirror image t-shortest
+        Some(shortest..=self.time - shortest)
+    }
+
+    // Binary search on the rising half of the distances
+    fn shortest_hold_search(&self) -> u128 {
+        let (mut low, mut high) = (0, self.time / 2);
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if self.beats_record(mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        low
+    }
+}
+
+// Largest r with r^2 <= n, by Newton's method
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut root = 1 << (n.ilog2() / 2 + 1);
+
+    loop {
+        let next = (root + n / root) / 2;
+
+        if next >= root {
+            return root;
+        }
+
+        root = next;
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    races: Vec<Race>,
+}
+
+impl Puzzle {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        Self::parse(&contents, false)
+    }
+
+    // Ignores the spaces in between the numbers, reading the table as one single race
+    pub fn load_kerned(contents: String) -> Result<Self, ParseError> {
+        Self::parse(&contents, true)
+    }
+
+    fn parse(contents: &str, kerned: bool) -> Result<Self, ParseError> {
+        let lines: Vec<Line> = Line::all(Day6::DAY, contents)
+            .filter(|line| !line.text.trim().is_empty())
+            .collect();
+        let missing = |label: &str| {
+            let number = contents.lines().count() + 1;
+            ParseError::new(Day6::DAY, number, 1, &format!("'{}'", label))
+        };
+
+        let time_line = lines.first().ok_or_else(|| missing("Time:"))?;
+        let dist_line = lines.get(1).ok_or_else(|| missing("Distance:"))?;
+        let times = Self::parse_row(time_line, "Time:", "race time", kerned)?;
+        let dists = Self::parse_row(dist_line, "Distance:", "record distance", kerned)?;
+
+        if let Some(line) = lines.get(2) {
+            return Err(line.error(line.text, "end of input"));
+        }
+
+        if dists.len() != times.len() {
+            return Err(dist_line.error(
+                dist_line.text,
+                &format!("{} record distances, one per race", times.len()),
+            ));
+        }
+
+        Ok(Self {
+            races: times
+                .into_iter()
+                .zip(dists)
+                .map(|(time, dist)| Race { time, dist })
+                .collect(),
+        })
+    }
+
+    // Numbers following the label of a row, or their digits joined together when kerned
+    fn parse_row(
+        line: &Line,
+        label: &str,
+        expected: &str,
+        kerned: bool,
+    ) -> Result<Vec<u128>, ParseError> {
+        let numbers = line
+            .text
+            .strip_prefix(label)
+            .ok_or_else(|| line.error(line.text, &format!("'{}'", label)))?;
+        let tokens = numbers
+            .split_whitespace()
+            .map(|token| line.parse::<u128>(token, expected).map(|_| token))
+            .collect::<Result<Vec<&str>, _>>()?;
+
+        match tokens.first() {
+            None => Err(line.error_at_end(expected)),
+            Some(first) if kerned => Ok(vec![tokens
+                .concat()
+                .parse::<u128>()
+                .map_err(|_| line.error(first, expected))?]),
+            Some(_) => Ok(tokens.iter().map(|token| token.parse().unwrap()).collect()),
+        }
+    }
+
+    // Product of the ways to win each race, which can outgrow a u128 with enough races
+    pub fn ways_to_win(&self) -> BigUint {
+        self.races
+            .iter()
+            .fold(BigUint::one(), |acc, race| acc * race.ways_to_win())
+    }
+
+    pub fn explain(&self) -> HoldReport {
+        HoldReport {
+            races: self
+                .races
+                .iter()
+                .zip(1..)
+                .map(|(race, number)| RaceReport {
+                    number,
+                    time: race.time,
+                    record: race.dist,
+                    holds: race.winning_holds(),
+                    ways: race.ways_to_win(),
+                    best_hold: race.best_hold(),
+                    max_distance: race.max_distance(),
+                    unwinnable_record: race.unwinnable_record(),
+                })
+                .collect(),
+            ways: self.ways_to_win(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RaceReport {
+    pub number: usize,
+    pub time: u128,
+    pub record: u128,
+    pub holds: Option<RangeInclusive<u128>>,
+    pub ways: u128,
+    pub best_hold: u128,
+    pub max_distance: Option<u128>,
+    pub unwinnable_record: Option<u128>,
+}
+
+#[derive(Debug)]
+pub struct HoldReport {
+    pub races: Vec<RaceReport>,
+    // Product of the ways to win every race
+    pub ways: BigUint,
+}
+
+impl HoldReport {
+    pub fn table(&self) -> String {
+        let or = |value: Option<u128>, none: &str| {
+            value.map_or(none.to_string(), |value| value.to_string())
+        };
+        let header = [
+            "race",
+            "time",
+            "record",
+            "winning holds",
+            "ways",
+            "best hold",
+            "max distance",
+            "unwinnable from",
+        ]
+        .map(|column| column.to_string());
+        let rows: Vec<[String; 8]> = self
+            .races
+            .iter()
+            .map(|race| {
+                [
+                    race.number.to_string(),
+                    race.time.to_string(),
+                    race.record.to_string(),
+                    race.holds.as_ref().map_or("none".to_string(), |holds| {
+                        format!("{}..={}", holds.start(), holds.end())
+                    }),
+                    race.ways.to_string(),
+                    race.best_hold.to_string(),
+                    or(race.max_distance, "overflow"),
+                    or(race.unwinnable_record, "never"),
+                ]
+            })
+            .collect();
+        let widths: Vec<usize> = (0..header.len())
+            .map(|column| {
+                rows.iter()
+                    .chain([&header])
+                    .map(|row| row[column].len())
+                    .max()
+                    .unwrap()
+            })
+            .collect();
+        let line = |row: &[String; 8]| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:>width$}", cell))
+                .collect::<Vec<String>>()
+                .join("  ")
+        };
+
+        let mut table = line(&header);
+        rows.iter().for_each(|row| {
+            table += "\n";
+            table += &line(row);
+        });
+        table += &format!("\nways to win: {}", self.ways);
+
+        table
+    }
+
+    pub fn json(&self) -> String {
+        let or_null =
+            |value: Option<u128>| value.map_or("null".to_string(), |value| value.to_string());
+        let races: Vec<String> = self
+            .races
+            .iter()
+            .map(|race| {
+                format!(
+                    r#"{{"race":{},"time":{},"record":{},"holds":{},"ways":{},"best_hold":{},"max_distance":{},"unwinnable_record":{}}}"#,
+                    race.number,
+                    race.time,
+                    race.record,
+                    race.holds.as_ref().map_or("null".to_string(), |holds| format!(
+                        r#"{{"first":{},"last":{}}}"#,
+                        holds.start(),
+                        holds.end()
+                    )),
+                    race.ways,
+                    race.best_hold,
+                    or_null(race.max_distance),
+                    or_null(race.unwinnable_record)
+                )
+            })
+            .collect();
+
+        format!(r#"{{"races":[{}],"ways":{}}}"#, races.join(","), self.ways)
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    // Separate races, and the kerned race of part 2. The kerned race can overflow where the
+    // separate races fit, so its error only fails part 2
+    type Input = (Puzzle, Result<Puzzle, ParseError>);
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Ok((
+            Puzzle::load(contents.to_string())?,
+            Puzzle::load_kerned(contents.to_string()),
+        ))
+    }
+
+    fn part1((puzzle, _): &Self::Input) -> impl Display {
+        puzzle.ways_to_win()
+    }
+
+    // Panics when the kerned race overflows, which try_part2 reports instead
+    fn part2((_, kerned): &Self::Input) -> impl Display {
+        kerned.as_ref().unwrap().ways_to_win()
+    }
+
+    fn try_part2((_, kerned): &Self::Input) -> Result<String, ParseError> {
+        kerned
+            .as_ref()
+            .map(|kerned| kerned.ways_to_win().to_string())
+            .map_err(Clone::clone)
+    }
+}
+
+impl Report for Day6 {
+    const FORMATS: &'static [&'static str] = &["table", "json"];
+
+    fn report(
+        (puzzle, kerned): &Self::Input,
+        part: u8,
+        format: &str,
+    ) -> Result<String, ParseError> {
+        let report = match part {
+            1 => puzzle.explain(),
+            _ => kerned.as_ref().map_err(Clone::clone)?.explain(),
+        };
+
+        Ok(match format {
+            "json" => report.json(),
+            _ => report.table(),
+        })
+    }
+}
+
+pub const SOLVER: Solver = Solver::reporting::<Day6>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_part1() {
+        let puzzle = Puzzle::load(fs::read_to_string("samples/day6.txt").unwrap()).unwrap();
+
+        assert_eq!(Race { time: 7, dist: 9 }.ways_to_win(), 4);
+        assert_eq!(Race { time: 15, dist: 40 }.ways_to_win(), 8);
+        assert_eq!(
+            Race {
+                time: 30,
+                dist: 200
+            }
+            .ways_to_win(),
+            9
+        );
+        assert_eq!(puzzle.ways_to_win(), BigUint::from(288u32));
+    }
+
+    #[test]
+    fn test_part2() {
+        let puzzle = Puzzle::load_kerned(fs::read_to_string("samples/day6.txt").unwrap()).unwrap();
+
+        assert_eq!(puzzle.races.len(), 1);
+        assert_eq!(puzzle.races[0].time, 71530);
+        assert_eq!(puzzle.races[0].dist, 940200);
+        assert_eq!(puzzle.ways_to_win(), BigUint::from(71503u32));
+    }
+
+    // Deterministic xorshift generator, so failing cases can be reproduced
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+    }
+
+    fn brute_force(race: &Race) -> u128 {
+        (0..=race.time)
+            .filter(|hold| hold * (race.time - hold) > race.dist)
+            .count() as u128
+    }
+
+    #[test]
+    fn test_isqrt() {
+        let mut rng = Rng(0x2023_1206);
+
+        for n in (0..1000).chain([u128::MAX, u128::MAX - 1, 1 << 126, (1 << 64) - 1]) {
+            let root = isqrt(n);
+            assert!(root * root <= n);
+            assert!((root + 1).checked_mul(root + 1).is_none_or(|sq| sq > n));
+        }
+
+        for _ in 0..1000 {
+            let root = rng.next() as u128;
+            assert_eq!(isqrt(root * root), root);
+            assert_eq!(isqrt(root * root + 2 * root), root);
+        }
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let mut rng = Rng(0x5eed);
+
+        for _ in 0..2000 {
+            let time = rng.below(500) as u128;
+            let dist = rng.below((time * time / 4 + 10) as u64) as u128;
+            let race = Race { time, dist };
+
+            assert_eq!(race.ways_to_win(), brute_force(&race), "{:?}", race);
+        }
+
+        // Records that tie with a hold time exactly
+        for _ in 0..2000 {
+            let time = rng.below(500) as u128;
+            let hold = rng.below(time as u64 + 1) as u128;
+            let race = Race {
+                time,
+                dist: hold * (time - hold),
+            };
+
+            assert_eq!(race.ways_to_win(), brute_force(&race), "{:?}", race);
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let mut rng = Rng(0xdead_beef);
+
+        // The boundary of the winning holds is exact far beyond f64 precision, and beyond t^2 fitting in a u128
+        for _ in 0..1000 {
+            let time = ((rng.next() as u128) << 64 | rng.next() as u128) >> rng.below(120) | 4;
+            let hold = ((rng.next() as u128) << 64 | rng.next() as u128)
+                % (time / 2).min(u128::MAX / time);
+            let race = Race {
+                time,
+                dist: hold * (time - hold),
+            };
+            let ways = race.ways_to_win();
+
+            assert_eq!(ways, time - 2 * (hold + 1) + 1, "{:?}", race);
+            assert_eq!(race.shortest_hold_search(), hold + 1, "{:?}", race);
+        }
+
+        // Every hold from 2 travels further than u128::MAX
+        assert_eq!(
+            Race {
+                time: u128::MAX,
+                dist: u128::MAX
+            }
+            .ways_to_win(),
+            u128::MAX - 3
+        );
+        assert_eq!(
+            Race {
+                time: u128::MAX,
+                dist: 0
+            }
+            .ways_to_win(),
+            u128::MAX - 1
+        );
+    }
+
+    #[test]
+    fn test_holds() {
+        let race = Race::new(7, 9);
+
+        assert_eq!(race.winning_holds(), Some(2..=5));
+        assert_eq!(race.best_hold(), 3);
+        assert_eq!(race.max_distance(), Some(12));
+        assert_eq!(
+            (0..=7).map(|hold| race.margin(hold)).collect::<Vec<_>>(),
+            vec![None, None, Some(1), Some(3), Some(3), Some(1), None, None]
+        );
+        assert_eq!(race.unwinnable_record(), Some(12));
+        assert_eq!(Race::new(7, 12).winning_holds(), None);
+        assert_eq!(Race::new(7, 11).winning_holds(), Some(3..=4));
+
+        assert_eq!(Race::new(30, 200).winning_holds(), Some(11..=19));
+        assert_eq!(Race::new(30, 200).unwinnable_record(), Some(225));
+        assert_eq!(Race::new(0, 0).winning_holds(), None);
+
+        let race = Race::new(u128::MAX, 5);
+        assert_eq!(race.winning_holds(), Some(1..=u128::MAX - 1));
+        assert_eq!(race.max_distance(), None);
+        assert_eq!(race.unwinnable_record(), None);
+        assert_eq!(race.margin(1), Some(u128::MAX - 6));
+        assert_eq!(race.margin(2), Some(u128::MAX - 5));
+
+        // Holds beyond the end of the race go nowhere
+        let race = Race::new(7, 9);
+        assert_eq!(race.distance(8), None);
+        assert_eq!(race.margin(8), None);
+        assert_eq!(race.margin(u128::MAX), None);
+        assert!(!race.beats_record(u128::MAX));
+
+        let mut rng = Rng(0x0606);
+        for _ in 0..500 {
+            let time = rng.below(200) as u128;
+            let race = Race::new(time, rng.below(time as u64 * time as u64 / 4 + 10) as u128);
+            let winning: Vec<u128> = (0..=time)
+                .filter(|&hold| race.margin(hold).is_some())
+                .collect();
+
+            assert_eq!(
+                race.winning_holds().map(|holds| holds.collect::<Vec<_>>()),
+                Some(winning).filter(|holds| !holds.is_empty()),
+                "{:?}",
+                race
+            );
+
+            // Any lower record can still be beaten
+            let record = race.unwinnable_record().unwrap();
+            assert_eq!(Race::new(time, record).ways_to_win(), 0);
+            assert!(record == 0 || Race::new(time, record - 1).ways_to_win() > 0);
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let contents = fs::read_to_string("samples/day6.txt").unwrap();
+        let input = Day6::parse(&contents).unwrap();
+
+        assert_eq!(
+            Day6::report(&input, 1, "table").unwrap(),
+            [
+                "race  time  record  winning holds  ways  best hold  max distance  unwinnable from",
+                "   1     7       9          2..=5     4          3            12               12",
+                "   2    15      40         4..=11     8          7            56               56",
+                "   3    30     200        11..=19     9         15           225              225",
+                "ways to win: 288",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            Day6::report(&input, 2, "json").unwrap(),
+            concat!(
+                r#"{"races":[{"race":1,"time":71530,"record":940200,"holds":{"first":14,"last":71516},"#,
+                r#""ways":71503,"best_hold":35765,"max_distance":1279135225,"unwinnable_record":1279135225}],"ways":71503}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_kerned_overflow() {
+        // 40 races that fit one by one, but not joined together
+        let contents = format!(
+            "Time: {}\nDistance: {}",
+            vec!["30"; 40].join(" "),
+            vec!["200"; 40].join(" ")
+        );
+        let input = Day6::parse(&contents).unwrap();
+
+        assert_eq!(Day6::try_part1(&input), Ok(9u128.pow(40).to_string()));
+        assert_eq!(
+            Day6::try_part2(&input),
+            Err(ParseError::new(6, 1, 7, "race time"))
+        );
+        assert_eq!(
+            (SOLVER.part2)(&contents),
+            Err(ParseError::new(6, 1, 7, "race time"))
+        );
+        assert_eq!(
+            (SOLVER.report.unwrap())(&contents, 2, "table"),
+            Err(ParseError::new(6, 1, 7, "race time"))
+        );
+
+        // The product of the ways to win outgrows a u128
+        let contents = format!(
+            "Time: {}\nDistance: {}",
+            vec!["30"; 60].join(" "),
+            vec!["200"; 60].join(" ")
+        );
+
+        assert_eq!(
+            (SOLVER.part1)(&contents),
+            Ok(BigUint::from(9u32).pow(60).to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Puzzle::load("Time:      7  15   30\nDistance:  9  4O  200".to_string()).unwrap_err(),
+            ParseError::new(6, 2, 15, "record distance")
+        );
+        assert_eq!(
+            Puzzle::load("Time:      7  15   30\nDistance:  9  40".to_string()).unwrap_err(),
+            ParseError::new(6, 2, 1, "3 record distances, one per race")
+        );
+        assert_eq!(
+            Puzzle::load("Time:      7  15   30\nRecord:  9  40  200".to_string()).unwrap_err(),
+            ParseError::new(6, 2, 1, "'Distance:'")
+        );
+        assert_eq!(
+            Puzzle::load("Time:      7  15   30".to_string()).unwrap_err(),
+            ParseError::new(6, 2, 1, "'Distance:'")
+        );
+        assert_eq!(
+            Puzzle::load_kerned(
+                "Time: 99999999999999999999 99999999999999999999\nDistance: 1 2".to_string()
+            )
+            .unwrap_err(),
+            ParseError::new(6, 1, 7, "race time")
+        );
+    }
+}
@@ -0,0 +1,620 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::iter::zip;
+
+// How hands of the same type are ordered
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TieBreak {
+    // Card by card in the order they were dealt
+    FirstCard,
+    // Strongest cards first, whatever their position
+    HighCard,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rules {
+    // Labels from weakest to strongest
+    labels: Vec<char>,
+    // Labels that stand in for whichever card makes the best hand
+    wildcards: HashSet<char>,
+    tie_break: TieBreak,
+}
+
+impl Rules {
+    pub fn new(labels: &str, wildcards: &str, tie_break: TieBreak) -> Self {
+        Self {
+            labels: labels.chars().collect(),
+            wildcards: wildcards.chars().collect(),
+            tie_break,
+        }
+    }
+
+    // Part 1 rules
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA", "", TieBreak::FirstCard)
+    }
+
+    // Part 2 rules, with jokers as the weakest cards
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", "J", TieBreak::FirstCard)
+    }
+
+    fn strength(&self, label: char) -> Option<u32> {
+        self.labels
+            .iter()
+            .position(|&other| other == label)
+            .map(|i| i as u32 + 1)
+    }
+}
+
+#[derive(Debug)]
+pub struct Card {
+    label: char,
+    strength: u32,
+    wild: bool,
+}
+
+impl Card {
+    pub fn new(label: char, rules: &Rules) -> Option<Self> {
+        Some(Self {
+            label,
+            strength: rules.strength(label)?,
+            wild: rules.wildcards.contains(&label),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct RankedHand<'b> {
+    identified_hand: &'b IdentifiedHand<'b>,
+    rank: u32,
+}
+
+impl RankedHand<'_> {
+    pub fn winnings(&self) -> u32 {
+        self.identified_hand.hand.bid * self.rank
+    }
+}
+
+#[derive(Debug)]
+pub struct IdentifiedHand<'a> {
+    hand: &'a Hand,
+    hand_type: HandType,
+}
+
+impl IdentifiedHand<'_> {
+    pub fn compare(&self, other: &Self, rules: &Rules) -> Ordering {
+        let ord = self.hand_type.cmp(&other.hand_type);
+
+        if ord.is_eq() {
+            let strengths = |hand: &Hand| {
+                let mut strengths: Vec<u32> = hand.cards.iter().map(|card| card.strength).collect();
+                if rules.tie_break == TieBreak::HighCard {
+                    strengths.sort_by(|a, b| b.cmp(a));
+                }
+                strengths
+            };
+
+            return zip(strengths(self.hand), strengths(other.hand))
+                .find_map(|(a, b)| {
+                    let result = a.cmp(&b);
+
+                    match result {
+                        Ordering::Equal => None,
+                        _ => Some(result),
+                    }
+                })
+                .unwrap_or(Ordering::Equal);
+        }
+
+        ord
+    }
+}
+
+#[derive(Debug)]
+pub struct Hand {
+    cards: Vec<Card>,
+    bid: u32,
+}
+
+impl Hand {
+    pub fn from_string(string: String, rules: &Rules) -> Result<Self, ParseError> {
+        let line = Line::new(Day7::DAY, 1, &string);
+        let (col_hand, col_bid) = line.split_once(" ", "' ' between hand and bid")?;
+
+        Ok(Self {
+            cards: col_hand
+                .char_indices()
+                .map(|(i, label)| {
+                    Card::new(label, rules).ok_or_else(|| line.error(&col_hand[i..], "card label"))
+                })
+                .collect::<Result<_, _>>()?,
+            bid: line.parse::<u32>(col_bid, "bid")?,
+        })
+    }
+
+    pub fn identify(&self) -> IdentifiedHand<'_> {
+        let label_freqs: HashMap<char, u32> =
+            self.cards
+                .iter()
+                .filter(|card| !card.wild)
+                .fold(HashMap::new(), |mut freqs, card| {
+                    match freqs.get(&card.label) {
+                        Some(count) => freqs.insert(card.label, count + 1),
+                        None => freqs.insert(card.label, 1),
+                    };
+                    freqs
+                });
+
+        let joker_count = self.cards.iter().filter(|card| card.wild).count() as u32;
+        let mut signature: Vec<u32> = label_freqs.into_values().collect();
+        signature.sort_by(|a, b| b.cmp(a));
+
+        // Jokers always do best joining the largest group
+        match signature.first_mut() {
+            Some(largest) => *largest += joker_count,
+            None if joker_count > 0 => signature.push(joker_count),
+            None => {}
+        }
+
+        IdentifiedHand {
+            hand: self,
+            hand_type: HandType { signature },
+        }
+    }
+}
+
+// Number of cards per label from the most to the least frequent, for hands of any size.
+// Comparing these lexicographically ranks five-card hands the way the puzzle does.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub struct HandType {
+    signature: Vec<u32>,
+}
+
+impl HandType {
+    pub fn new(signature: &[u32]) -> Self {
+        let mut signature = signature.to_vec();
+        signature.sort_by(|a, b| b.cmp(a));
+
+        Self { signature }
+    }
+
+    pub fn signature(&self) -> &[u32] {
+        &self.signature
+    }
+
+    // Name of the labels that occur more than once, which are sorted from largest to smallest group
+    fn name(groups: &[u32]) -> String {
+        match groups {
+            [] => "high card".to_string(),
+            [3, 2] => "full house".to_string(),
+            [3, 3] => "two triples".to_string(),
+            [size] if *size > 2 => format!("{} of a kind", count_word(*size as usize)),
+            _ if groups.iter().all(|&size| size == 2) => {
+                format!("{} pair", count_word(groups.len()))
+            }
+            [first, rest @ ..] => format!("{} and {}", Self::name(&[*first]), Self::name(rest)),
+        }
+    }
+}
+
+impl Display for HandType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let groups: Vec<u32> = self
+            .signature
+            .iter()
+            .copied()
+            .filter(|&size| size > 1)
+            .collect();
+
+        write!(f, "{}", Self::name(&groups))
+    }
+}
+
+fn count_word(count: usize) -> String {
+    const WORDS: [&str; 11] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+
+    WORDS
+        .get(count)
+        .map_or(count.to_string(), |word| word.to_string())
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    hands: Vec<Hand>,
+    rules: Rules,
+}
+
+impl Puzzle {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        Self::with_rules(contents, Rules::standard())
+    }
+
+    pub fn load_pt2(contents: String) -> Result<Self, ParseError> {
+        Self::with_rules(contents, Rules::jokers())
+    }
+
+    pub fn with_rules(contents: String, rules: Rules) -> Result<Self, ParseError> {
+        let hands: Vec<Hand> = contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                Hand::from_string(line.to_string(), &rules).map_err(|err| err.at_line(i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        // Hands can be any size, as long as they all have the same one
+        if let Some(first) = hands.first() {
+            let size = first.cards.len();
+
+            if let Some((i, hand)) = hands
+                .iter()
+                .enumerate()
+                .find(|(_, hand)| hand.cards.len() != size)
+            {
+                return Err(ParseError::new(
+                    Day7::DAY,
+                    i + 1,
+                    hand.cards.len().min(size) + 1,
+                    &format!("{} cards, like the first hand", size),
+                ));
+            }
+        }
+
+        Ok(Self { hands, rules })
+    }
+
+    pub fn total_winnings(&self) -> u32 {
+        let mut identified_hands = self
+            .hands
+            .iter()
+            .map(|hand| hand.identify())
+            .collect::<Vec<IdentifiedHand>>();
+        identified_hands.sort_by(|a, b| a.compare(b, &self.rules));
+        identified_hands
+            .iter()
+            .enumerate()
+            .map(|(i, identified_hand)| RankedHand {
+                identified_hand,
+                rank: (i + 1) as u32,
+            })
+            .map(|ranked_hand| ranked_hand.winnings())
+            .sum()
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = (Puzzle, Puzzle);
+
+    fn parse(contents: &str) -> Result<(Puzzle, Puzzle), ParseError> {
+        Ok((
+            Puzzle::load(contents.to_string())?,
+            Puzzle::load_pt2(contents.to_string())?,
+        ))
+    }
+
+    fn part1(puzzles: &(Puzzle, Puzzle)) -> impl Display {
+        puzzles.0.total_winnings()
+    }
+
+    fn part2(puzzles: &(Puzzle, Puzzle)) -> impl Display {
+        puzzles.1.total_winnings()
+    }
+}
+
+pub const SOLVER: Solver = Solver::of::<Day7>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Every digit as a card, with '*' as the joker
+    fn legacy() -> Rules {
+        Rules::new("*123456789TJQKA", "*", TieBreak::FirstCard)
+    }
+
+    fn hand(string: &str) -> Hand {
+        Hand::from_string(string.to_string(), &legacy()).unwrap()
+    }
+
+    #[test]
+    fn test_ranking() {}
+
+    #[test]
+    fn test_common() {
+        assert_eq!(
+            hand("KKKKK 123").identify().hand_type.to_string(),
+            "five of a kind"
+        );
+        assert_eq!(
+            hand("KKKKA 123").identify().hand_type.to_string(),
+            "four of a kind"
+        );
+        assert_eq!(
+            hand("KKKAA 123").identify().hand_type.to_string(),
+            "full house"
+        );
+        assert_eq!(
+            hand("KKKAQ 123").identify().hand_type.to_string(),
+            "three of a kind"
+        );
+        assert_eq!(
+            hand("KTJJT 123").identify().hand_type.to_string(),
+            "two pair"
+        );
+        assert_eq!(
+            hand("KKAJQ 123").identify().hand_type.to_string(),
+            "one pair"
+        );
+        assert_eq!(
+            hand("KTAJQ 123").identify().hand_type.to_string(),
+            "high card"
+        );
+
+        // Compare strengths for cards with equal types
+        let hand_1 = IdentifiedHand {
+            hand: &hand("22222 123"),
+            hand_type: HandType::new(&[1, 1, 1, 1, 1]),
+        };
+        let hand_2 = IdentifiedHand {
+            hand: &hand("11111 123"),
+            hand_type: HandType::new(&[1, 1, 1, 1, 1]),
+        };
+        let hand_3 = IdentifiedHand {
+            hand: &hand("33333 123"),
+            hand_type: HandType::new(&[1, 1, 1, 1, 1]),
+        };
+        assert_eq!(hand_1.compare(&hand_1, &legacy()), Ordering::Equal);
+        assert_eq!(hand_1.compare(&hand_2, &legacy()), Ordering::Greater);
+        assert_eq!(hand_1.compare(&hand_3, &legacy()), Ordering::Less);
+
+        // Specific case from sample (2x two pairs -> compare strengths)
+        assert_eq!(
+            IdentifiedHand {
+                hand: &hand("KK677 28"),
+                hand_type: HandType::new(&[1, 1, 1, 1, 1]),
+            }
+            .compare(
+                &IdentifiedHand {
+                    hand: &hand("KTJJT 220"),
+                    hand_type: HandType::new(&[1, 1, 1, 1, 1]),
+                },
+                &legacy()
+            ),
+            Ordering::Greater
+        );
+
+        assert_eq!(
+            IdentifiedHand {
+                hand: &hand("JJJJJ 666"),
+                hand_type: HandType::new(&[5]),
+            }
+            .compare(
+                &IdentifiedHand {
+                    hand: &hand("99979 459"),
+                    hand_type: HandType::new(&[4, 1]),
+                },
+                &legacy()
+            ),
+            Ordering::Greater
+        );
+
+        assert_eq!(
+            IdentifiedHand {
+                hand: &hand("99959 922"),
+                hand_type: HandType::new(&[4, 1]),
+            }
+            .compare(
+                &IdentifiedHand {
+                    hand: &hand("99899 277"),
+                    hand_type: HandType::new(&[4, 1]),
+                },
+                &legacy()
+            ),
+            Ordering::Greater
+        );
+
+        assert_eq!(
+            IdentifiedHand {
+                hand: &hand("99J99 377"),
+                hand_type: HandType::new(&[4, 1]),
+            }
+            .compare(
+                &IdentifiedHand {
+                    hand: &hand("99899 288"),
+                    hand_type: HandType::new(&[4, 1]),
+                },
+                &legacy()
+            ),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_joker() {
+        assert_eq!(
+            hand("*KKKK 123").identify().hand_type.to_string(),
+            "five of a kind"
+        );
+        assert_eq!(
+            hand("1*KKK 123").identify().hand_type.to_string(),
+            "four of a kind"
+        );
+        assert_eq!(
+            hand("11*KK 123").identify().hand_type.to_string(),
+            "full house"
+        );
+        assert_eq!(
+            hand("12*KK 123").identify().hand_type.to_string(),
+            "three of a kind"
+        );
+        assert_eq!(
+            hand("12**K 123").identify().hand_type.to_string(),
+            "three of a kind"
+        );
+        assert_eq!(
+            hand("12*** 123").identify().hand_type.to_string(),
+            "four of a kind"
+        );
+        assert_eq!(
+            hand("1**** 123").identify().hand_type.to_string(),
+            "five of a kind"
+        );
+        assert_eq!(
+            hand("***** 123").identify().hand_type.to_string(),
+            "five of a kind"
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let sample = fs::read_to_string("samples/day7.txt").unwrap();
+
+        // J stays a plain card unless the rules make it wild
+        let hand = Hand::from_string("QJJQ2 1".to_string(), &Rules::standard()).unwrap();
+        assert_eq!(hand.identify().hand_type.to_string(), "two pair");
+        let hand = Hand::from_string("QJJQ2 1".to_string(), &Rules::jokers()).unwrap();
+        assert_eq!(hand.identify().hand_type.to_string(), "four of a kind");
+        assert_eq!(hand.cards[1].strength, 1);
+        assert_eq!(
+            Puzzle::load_pt2("32T3* 765".to_string()).unwrap_err(),
+            ParseError::new(7, 1, 5, "card label")
+        );
+
+        // Same rules as part 2, given explicitly
+        let rules = Rules::new("J23456789TQKA", "J", TieBreak::FirstCard);
+        assert_eq!(
+            Puzzle::with_rules(sample.clone(), rules)
+                .unwrap()
+                .total_winnings(),
+            5905
+        );
+
+        // Aces low with deuces wild, ranked poker-style on the strongest cards
+        let rules = Rules::new("A23456789TJQK", "2", TieBreak::HighCard);
+        let first = Hand::from_string("K3A4Q 1".to_string(), &rules).unwrap();
+        let second = Hand::from_string("3KQ4A 2".to_string(), &rules).unwrap();
+        assert_eq!(
+            first.identify().compare(&second.identify(), &rules),
+            Ordering::Equal
+        );
+        let third = Hand::from_string("QJ2A3 3".to_string(), &rules).unwrap();
+        assert_eq!(third.identify().hand_type.to_string(), "one pair");
+        assert_eq!(
+            Puzzle::with_rules("K3A4Q 10\nQJ2A3 20\n5TA87 30".to_string(), rules)
+                .unwrap()
+                .total_winnings(),
+            30 + 10 * 2 + 20 * 3
+        );
+    }
+
+    #[test]
+    fn test_sizes() {
+        let hand_type = |string: &str| {
+            Hand::from_string(string.to_string(), &Rules::jokers())
+                .unwrap()
+                .identify()
+                .hand_type
+        };
+
+        assert_eq!(hand_type("KKK 1").to_string(), "three of a kind");
+        assert_eq!(hand_type("KQK 1").to_string(), "one pair");
+        assert_eq!(hand_type("KQJ 1").to_string(), "one pair");
+        assert_eq!(hand_type("K2Q 1").to_string(), "high card");
+        assert_eq!(hand_type("KKQQ 1").to_string(), "two pair");
+        assert_eq!(hand_type("KKKQQQ2 1").to_string(), "two triples");
+        assert_eq!(
+            hand_type("KKKQQJ2 1").to_string(),
+            "four of a kind and one pair"
+        );
+        assert_eq!(hand_type("KKKQQ23 1").to_string(), "full house");
+        assert_eq!(
+            hand_type("KKKQQ22 1").to_string(),
+            "three of a kind and two pair"
+        );
+        assert_eq!(hand_type("KKQQ223 1").to_string(), "three pair");
+        assert_eq!(
+            hand_type("KKKKQQQ 1").to_string(),
+            "four of a kind and three of a kind"
+        );
+        assert_eq!(hand_type("KKKQQ2J 1").signature(), &[4, 2, 1]);
+        assert_eq!(hand_type("JJJJJJJ 1").to_string(), "seven of a kind");
+
+        // The larger the groups, the stronger the hand
+        let mut signatures = [
+            vec![3, 3, 1],
+            vec![1, 1, 1, 1, 1, 1, 1],
+            vec![4, 3],
+            vec![3, 2, 2],
+            vec![2, 2, 2, 1],
+            vec![7],
+            vec![3, 2, 1, 1],
+            vec![4, 1, 1, 1],
+        ]
+        .map(|signature| HandType::new(&signature));
+        signatures.sort();
+        assert_eq!(
+            signatures.map(|hand_type| hand_type.to_string()),
+            [
+                "high card",
+                "three pair",
+                "full house",
+                "three of a kind and two pair",
+                "two triples",
+                "four of a kind",
+                "four of a kind and three of a kind",
+                "seven of a kind",
+            ]
+        );
+
+        assert_eq!(
+            Puzzle::load("KK2 1\nQ3Q 2\n333 3\nA45 4".to_string())
+                .unwrap()
+                .total_winnings(),
+            4 + 2 * 2 + 3 + 3 * 4
+        );
+        assert_eq!(
+            Puzzle::load("32T3K 765\nT55J 684".to_string()).unwrap_err(),
+            ParseError::new(7, 2, 5, "5 cards, like the first hand")
+        );
+        assert_eq!(
+            Puzzle::load("32T 765\nT55 684\nT55J5 684".to_string()).unwrap_err(),
+            ParseError::new(7, 3, 4, "3 cards, like the first hand")
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let puzzle = Puzzle::load(fs::read_to_string("samples/day7.txt").unwrap()).unwrap();
+        assert_eq!(puzzle.total_winnings(), 6440);
+    }
+
+    #[test]
+    fn test_part2() {
+        let puzzle = Puzzle::load_pt2(fs::read_to_string("samples/day7.txt").unwrap()).unwrap();
+        assert_eq!(puzzle.total_winnings(), 5905);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Puzzle::load("32T3K 765\nT55X5 684".to_string()).unwrap_err(),
+            ParseError::new(7, 2, 4, "card label")
+        );
+        assert_eq!(
+            Puzzle::load("32T3K765".to_string()).unwrap_err(),
+            ParseError::new(7, 1, 9, "' ' between hand and bid")
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use num::integer::lcm;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+pub struct Map {
+    directions: Vec<Direction>,
+    nodes: Vec<Node>,
+    // Number of lines in the input, to point past its end when a node is missing
+    lines: usize,
+}
+
+impl Map {
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        let mut lines = Line::all(Day8::DAY, &contents);
+        let directions = lines
+            .next()
+            .ok_or_else(|| ParseError::new(Day8::DAY, 1, 1, "directions"))?;
+
+        if directions.text.is_empty() {
+            // Without any directions the walk never gets anywhere
+            return Err(directions.error_at_end("directions"));
+        }
+
+        let directions = directions
+            .text
+            .char_indices()
+            .map(|(i, char)| match char {
+                'L' => Ok(Direction::Left),
+                'R' => Ok(Direction::Right),
+                _ => Err(directions.error(&directions.text[i..], "'L' or 'R'")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let nodes: Vec<(Line, &str, &str, &str)> = lines
+            .filter(|line| !line.text.is_empty())
+            .map(|line| {
+                let (label, targets) = line.split_once(" = (", "' = (' after the node label")?;
+                let (left, right) = targets
+                    .strip_suffix(')')
+                    .ok_or_else(|| line.error_at_end("')'"))?
+                    .split_once(", ")
+                    .ok_or_else(|| line.error(targets, "'<left>, <right>'"))?;
+
+                Ok((line, label, left, right))
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        // Every node has to lead somewhere on the map
+        let labels: HashSet<&str> = nodes.iter().map(|(_, label, _, _)| *label).collect();
+        for (line, _, left, right) in &nodes {
+            if let Some(target) = [left, right]
+                .into_iter()
+                .find(|target| !labels.contains(*target))
+            {
+                return Err(line.error(target, "label of a node on the map"));
+            }
+        }
+
+        Ok(Self {
+            directions,
+            lines: contents.lines().count(),
+            nodes: nodes
+                .into_iter()
+                .map(|(_, label, left, right)| Node {
+                    label: label.to_string(),
+                    left: left.to_string(),
+                    right: right.to_string(),
+                })
+                .collect(),
+        })
+    }
+
+    // Fails at the end of the input when the map doesn't have the given node
+    pub fn require(&self, label: &str) -> Result<&Node, ParseError> {
+        self.node(&label.to_string()).ok_or_else(|| {
+            ParseError::new(Day8::DAY, self.lines + 1, 1, &format!("node '{}'", label))
+        })
+    }
+
+    pub fn node(&self, label: &String) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.label.eq(label))
+    }
+
+    pub fn next_node(&self, node: &Node, direction: &Direction) -> &Node {
+        self.node(node.direction_label(direction)).unwrap()
+    }
+
+    pub fn apply_directions<'a>(&'a self, start: &'a Node) -> &'a Node {
+        self.directions
+            .iter()
+            .fold(start, |node, direction| self.next_node(node, direction))
+    }
+
+    pub fn steps_pt1(&self, node: &Node) -> u64 {
+        let mut iterations = 1;
+        let mut current = node;
+
+        loop {
+            current = self.apply_directions(current);
+
+            if current.label == "ZZZ" {
+                break;
+            }
+
+            iterations += 1;
+        }
+
+        iterations * self.directions.len() as u64
+    }
+
+    // Only maps with both AAA and ZZZ have a walk between them
+    pub fn part1(&self) -> Result<u64, ParseError> {
+        let start = self.require("AAA")?;
+        self.require("ZZZ")?;
+
+        Ok(self.steps_pt1(start))
+    }
+
+    pub fn steps_pt2(&self, node: &Node) -> u64 {
+        let mut steps = 0;
+        let mut current = node;
+
+        loop {
+            match self
+                .directions
+                .iter()
+                .enumerate()
+                .find_map(|(i, direction)| {
+                    current = self.next_node(current, direction);
+
+                    if current.label.ends_with("Z") {
+                        return Some(i);
+                    }
+
+                    None
+                }) {
+                None => steps += self.directions.len(),
+                Some(i) => {
+                    steps += i + 1;
+                    break;
+                }
+            }
+        }
+
+        steps as u64
+    }
+
+    pub fn part2(&self) -> u64 {
+        self.nodes
+            .iter()
+            .filter(|node| node.label.ends_with("A"))
+            .map(|node| self.steps_pt2(node))
+            .reduce(lcm)
+            .unwrap()
+    }
+}
+
+#[derive(Debug)]
+pub struct Node {
+    label: String,
+    left: String,
+    right: String,
+}
+
+impl Node {
+    pub fn direction_label(&self, direction: &Direction) -> &String {
+        match direction {
+            Direction::Left => &self.left,
+            _ => &self.right,
+        }
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Map;
+
+    fn parse(contents: &str) -> Result<Map, ParseError> {
+        Map::load(contents.to_string())
+    }
+
+    // Panics on a map without AAA or ZZZ, which try_part1 reports instead
+    fn part1(map: &Map) -> impl Display {
+        map.part1().unwrap()
+    }
+
+    fn try_part1(map: &Map) -> Result<String, ParseError> {
+        map.part1().map(|steps| steps.to_string())
+    }
+
+    fn part2(map: &Map) -> impl Display {
+        map.part2()
+    }
+}
+
+pub const SOLVER: Solver = Solver::of::<Day8>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Map::load(fs::read_to_string("samples/day8a.txt").unwrap())
+                .unwrap()
+                .part1(),
+            Ok(2)
+        );
+        assert_eq!(
+            Map::load(fs::read_to_string("samples/day8b.txt").unwrap())
+                .unwrap()
+                .part1(),
+            Ok(6)
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Map::load(fs::read_to_string("samples/day8c.txt").unwrap())
+                .unwrap()
+                .part2(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Map::load("LLX\n\nAAA = (BBB, BBB)".to_string()).unwrap_err(),
+            ParseError::new(8, 1, 3, "'L' or 'R'")
+        );
+        assert_eq!(
+            Map::load("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA ZZZ)".to_string()).unwrap_err(),
+            ParseError::new(8, 4, 8, "'<left>, <right>'")
+        );
+        assert_eq!(
+            Map::load("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, ZZZ)".to_string()).unwrap_err(),
+            ParseError::new(8, 3, 13, "label of a node on the map")
+        );
+        assert_eq!(
+            Map::load("\n\nAAA = (AAA, AAA)".to_string()).unwrap_err(),
+            ParseError::new(8, 1, 1, "directions")
+        );
+        assert_eq!(
+            (SOLVER.part1)("LR\n\nBBB = (ZZZ, BBB)\nZZZ = (ZZZ, ZZZ)\n"),
+            Err(ParseError::new(8, 5, 1, "node 'AAA'"))
+        );
+        assert_eq!(
+            (SOLVER.part1)("LR\n\nAAA = (AAA, AAA)"),
+            Err(ParseError::new(8, 4, 1, "node 'ZZZ'"))
+        );
+        assert!(Day8::parse(&fs::read_to_string("samples/day8a.txt").unwrap()).is_ok());
+
+        // Part 2 doesn't need AAA or ZZZ
+        let contents = fs::read_to_string("samples/day8c.txt").unwrap();
+        assert_eq!((SOLVER.part2)(&contents), Ok("6".to_string()));
+    }
+}
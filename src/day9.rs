@@ -1,7 +1,10 @@
-use std::fs;
+use crate::error::{Line, ParseError};
+use crate::registry::Solver;
+use crate::solution::Solution;
+use std::fmt::Display;
 
 #[derive(Debug, Clone)]
-struct Sequence {
+pub struct Sequence {
     numbers: Vec<i64>,
 }
 
@@ -44,34 +47,39 @@ impl Sequence {
         let first_numbers = self
             .increments_stack()
             .iter()
-            .map(|increments| increments.numbers.first().unwrap().clone())
+            .map(|increments| *increments.numbers.first().unwrap())
             .collect::<Vec<i64>>();
 
-        self.numbers.first().unwrap()
-            - first_numbers[0..first_numbers.len() - 1]
-                .iter()
-                .rfold(first_numbers.last().unwrap().clone(), |acc, num| num - acc)
+        // A constant sequence has no increments to go back through
+        self.numbers.first().unwrap() - first_numbers.iter().rfold(0, |acc, num| num - acc)
     }
 }
 
 #[derive(Debug)]
-struct Report {
+pub struct Report {
     sequences: Vec<Sequence>,
 }
 
 impl Report {
-    pub fn load(contents: String) -> Self {
-        Report {
-            sequences: contents
-                .lines()
-                .map(|line| Sequence {
-                    numbers: line
+    pub fn load(contents: String) -> Result<Self, ParseError> {
+        Ok(Report {
+            sequences: Line::all(Day9::DAY, &contents)
+                .map(|line| {
+                    let numbers = line
+                        .text
                         .split_whitespace()
-                        .map(|number| number.parse::<i64>().expect("Invalid number detected"))
-                        .collect::<Vec<i64>>(),
+                        .map(|number| line.parse::<i64>(number, "number"))
+                        .collect::<Result<Vec<i64>, _>>()?;
+
+                    // Increments only start to tell where a sequence goes from two numbers on
+                    if numbers.len() < 2 {
+                        return Err(line.error_at_end("at least two numbers"));
+                    }
+
+                    Ok(Sequence { numbers })
                 })
-                .collect(),
-        }
+                .collect::<Result<_, _>>()?,
+        })
     }
 
     pub fn part1(&self) -> i64 {
@@ -89,20 +97,32 @@ impl Report {
     }
 }
 
-fn main() {
-    println!(
-        "Part 1: {}",
-        Report::load(fs::read_to_string("inputs/day9.txt").unwrap()).part1()
-    );
-    println!(
-        "Part 2: {}",
-        Report::load(fs::read_to_string("inputs/day9.txt").unwrap()).part2()
-    );
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Report;
+
+    fn parse(contents: &str) -> Result<Report, ParseError> {
+        Report::load(contents.to_string())
+    }
+
+    fn part1(report: &Report) -> impl Display {
+        report.part1()
+    }
+
+    fn part2(report: &Report) -> impl Display {
+        report.part2()
+    }
 }
 
+pub const SOLVER: Solver = Solver::of::<Day9>();
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_sequence() {
@@ -164,12 +184,21 @@ mod tests {
             .prev_num(),
             5
         );
+        assert_eq!(
+            Sequence {
+                numbers: vec![3, 3, 3]
+            }
+            .prev_num(),
+            3
+        );
     }
 
     #[test]
     fn test_part1() {
         assert_eq!(
-            Report::load(fs::read_to_string("samples/day9.txt").unwrap()).part1(),
+            Report::load(fs::read_to_string("samples/day9.txt").unwrap())
+                .unwrap()
+                .part1(),
             114
         );
     }
@@ -177,8 +206,26 @@ mod tests {
     #[test]
     fn test_part2() {
         assert_eq!(
-            Report::load(fs::read_to_string("samples/day9.txt").unwrap()).part2(),
+            Report::load(fs::read_to_string("samples/day9.txt").unwrap())
+                .unwrap()
+                .part2(),
             2
         );
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Report::load("0 3 6 9\n1 3 - 10".to_string()).unwrap_err(),
+            ParseError::new(9, 2, 5, "number")
+        );
+        assert_eq!(
+            Report::load("0 3 6 9\n\n1 3 6 10".to_string()).unwrap_err(),
+            ParseError::new(9, 2, 1, "at least two numbers")
+        );
+        assert_eq!(
+            (SOLVER.part2)("0 3 6 9\n7"),
+            Err(ParseError::new(9, 2, 2, "at least two numbers"))
+        );
+    }
 }
@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// Points at the place in an input file where parsing failed (line and column are 1-based)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, expected: &str) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            expected: expected.to_string(),
+        }
+    }
+
+    // Moves an error reported by a single-line parser to the line it was read from
+    pub fn at_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+
+    // Moves an error reported by a parser of a slice to where that slice starts within its line
+    pub fn offset_columns(self, columns: usize) -> Self {
+        Self {
+            column: self.column + columns,
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {} input, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+// A single line of an input file, used to report errors at the offending token
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(day: u8, number: usize, text: &'a str) -> Self {
+        Self { day, number, text }
+    }
+
+    // All lines of the contents, numbered from 1
+    pub fn all(day: u8, contents: &'a str) -> impl Iterator<Item = Line<'a>> {
+        contents
+            .lines()
+            .enumerate()
+            .map(move |(i, text)| Self::new(day, i + 1, text))
+    }
+
+    // Column of a token that is a slice of this line, or the end of the line for any other token
+    pub fn column(&self, token: &str) -> usize {
+        let offset = (token.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+
+        if offset <= self.text.len() {
+            offset + 1
+        } else {
+            self.text.len() + 1
+        }
+    }
+
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        ParseError::new(self.day, self.number, self.column(token), expected)
+    }
+
+    // Error at the end of the line, for tokens that are missing altogether
+    pub fn error_at_end(&self, expected: &str) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        token.parse::<T>().map_err(|_| self.error(token, expected))
+    }
+
+    pub fn split_once(
+        &self,
+        delimiter: &str,
+        expected: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error_at_end(expected))
+    }
+
+    // Fails at the first character that isn't one of the allowed characters
+    pub fn expect_chars(&self, allowed: &str, expected: &str) -> Result<(), ParseError> {
+        match self.text.find(|char| !allowed.contains(char)) {
+            Some(i) => Err(self.error(&self.text[i..], expected)),
+            None => Ok(()),
+        }
+    }
+
+    // Fails where a row of a grid is shorter or longer than the rows before it, counting characters
+    pub fn expect_width(&self, width: usize) -> Result<(), ParseError> {
+        if self.text.chars().count() == width {
+            return Ok(());
+        }
+
+        let end = self
+            .text
+            .char_indices()
+            .nth(width)
+            .map_or(self.text.len(), |(i, _)| i);
+
+        Err(self.error(&self.text[end..], &format!("row of {} tiles", width)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line() {
+        let line = Line::new(4, 2, "Card 1: 41 4x | 83");
+        let (_, numbers) = line.split_once(": ", "': '").unwrap();
+        let token = numbers.split_ascii_whitespace().nth(1).unwrap();
+
+        assert_eq!(line.column(token), 12);
+        assert_eq!(
+            line.parse::<u32>(token, "winning number"),
+            Err(ParseError::new(4, 2, 12, "winning number"))
+        );
+        assert_eq!(
+            line.split_once(" ; ", "';'"),
+            Err(ParseError::new(4, 2, 19, "';'"))
+        );
+        assert_eq!(
+            Line::new(13, 1, "#.##..#").expect_chars(".#", "'.' or '#'"),
+            Ok(())
+        );
+        assert_eq!(
+            Line::new(13, 1, "#.#O..#").expect_chars(".#", "'.' or '#'"),
+            Err(ParseError::new(13, 1, 4, "'.' or '#'"))
+        );
+        assert_eq!(
+            Line::new(14, 3, "O.#").expect_width(5),
+            Err(ParseError::new(14, 3, 4, "row of 5 tiles"))
+        );
+        assert_eq!(
+            ParseError::new(4, 1, 12, "winning number")
+                .at_line(2)
+                .to_string(),
+            "Day 4 input, line 2, column 12: expected winning number"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use crate::error::{Line, ParseError};
+use std::fmt::{Display, Formatter};
+
+// (x, y) with the origin in the top left corner
+pub type Pos = (usize, usize);
+
+// Offsets of the 4 orthogonal neighbours: up, down, left, right
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+// Offsets of the 8 surrounding neighbours, row by row
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "All rows of a grid must have the same width"
+        );
+
+        Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        }
+    }
+
+    // Parses one tile per character, ignoring blank lines before and after the grid
+    pub fn parse(
+        day: u8,
+        contents: &str,
+        tile: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let lines: Vec<Line> = Line::all(day, contents)
+            .skip_while(|line| line.text.trim().is_empty())
+            .collect();
+        let lines = &lines[..lines
+            .iter()
+            .rposition(|line| !line.text.trim().is_empty())
+            .map_or(0, |i| i + 1)];
+        let width = lines.first().map_or(0, |line| line.text.chars().count());
+
+        if lines.is_empty() {
+            return Err(ParseError::new(day, 1, 1, "at least one row"));
+        }
+
+        Ok(Self::from_rows(
+            lines
+                .iter()
+                .map(|line| {
+                    line.expect_width(width)?;
+                    line.text
+                        .char_indices()
+                        .map(|(i, char)| {
+                            tile(char).ok_or_else(|| line.error(&line.text[i..], expected))
+                        })
+                        .collect::<Result<Vec<T>, _>>()
+                })
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index(pos).map(|i| &mut self.cells[i])
+    }
+
+    fn index(&self, (x, y): Pos) -> Option<usize> {
+        if self.contains((x, y)) {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    // Position at an offset from another position, if it is within the grid
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+
+        self.contains(pos).then_some(pos)
+    }
+
+    // All positions in reading order (row by row)
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn render(&self, tile: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&tile).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Mirrors the grid over its main diagonal, turning rows into columns
+    pub fn transpose(&self) -> Self {
+        Self::from_rows(self.columns().map(|col| col.cloned().collect()).collect())
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_rows(
+            self.columns()
+                .map(|col| col.cloned().collect::<Vec<T>>().into_iter().rev().collect())
+                .collect(),
+        )
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_rows(
+            self.columns()
+                .map(|col| col.cloned().collect())
+                .collect::<Vec<Vec<T>>>()
+                .into_iter()
+                .rev()
+                .collect(),
+        )
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|&char| char))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse(0, "\nab.\n.cd\n\n", Some, "tile").unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = sample();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.to_string(), "ab.\n.cd");
+        assert_eq!(
+            Grid::parse(0, "..\n.#", |char| (char == '.').then_some(()), "'.'"),
+            Err(ParseError::new(0, 2, 2, "'.'"))
+        );
+        assert_eq!(
+            Grid::parse(0, "...\n..", Some, "tile"),
+            Err(ParseError::new(0, 2, 3, "row of 3 tiles"))
+        );
+        // Widths count characters, not bytes
+        assert_eq!(
+            Grid::parse(0, "a€\nabcd", Some, "tile"),
+            Err(ParseError::new(0, 2, 3, "row of 2 tiles"))
+        );
+        assert_eq!(
+            Grid::parse(0, "abcd\na€", Some, "tile"),
+            Err(ParseError::new(0, 2, 5, "row of 4 tiles"))
+        );
+        assert_eq!(
+            Grid::parse(0, "a€\n€b", Some, "tile").map(|grid| grid.to_string()),
+            Ok("a€\n€b".to_string())
+        );
+        assert_eq!(
+            Grid::parse(0, "\n\n", Some, "tile"),
+            Err(ParseError::new(0, 1, 1, "at least one row"))
+        );
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = sample();
+
+        assert_eq!(grid.get((1, 1)), Some(&'c'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        *grid.get_mut((2, 0)).unwrap() = 'x';
+        assert_eq!(grid.row(0), &['a', 'b', 'x']);
+        assert_eq!(grid.column(1).collect::<String>(), "bc");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, &char)| char == '.')
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>(),
+            vec![(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = sample();
+
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours_4((1, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1), (2, 1)]
+        );
+        assert_eq!(grid.neighbours_8((1, 0)).count(), 5);
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = sample();
+
+        assert_eq!(grid.transpose().to_string(), "a.\nbc\n.d");
+        assert_eq!(grid.rotate_cw().to_string(), ".a\ncb\nd.");
+        assert_eq!(grid.rotate_ccw().to_string(), ".d\nbc\na.");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(
+            grid.map(|(x, y), _| x + y)
+                .render(|n| char::from_digit(*n as u32, 10).unwrap()),
+            "012\n123"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use crate::error::ParseError;
+use crate::solution::{Report, Solution};
+use crate::*;
+
+// Solves one part of a puzzle given the raw contents of an input file
+pub type PartFn = fn(&str) -> Result<String, ParseError>;
+
+// Reports on one part of a puzzle given the raw contents of an input file and an output format
+pub type ReportFn = fn(&str, u8, &str) -> Result<String, ParseError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    pub part1: PartFn,
+    pub part2: PartFn,
+    pub report: Option<ReportFn>,
+    pub formats: &'static [&'static str],
+}
+
+impl Solver {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            part1: |contents| S::parse(contents).and_then(|input| S::try_part1(&input)),
+            part2: |contents| S::parse(contents).and_then(|input| S::try_part2(&input)),
+            report: None,
+            formats: &[],
+        }
+    }
+
+    pub const fn reporting<S: Report>() -> Self {
+        Self {
+            report: Some(|contents, part, format| {
+                S::parse(contents).and_then(|input| S::report(&input, part, format))
+            }),
+            formats: S::FORMATS,
+            ..Self::of::<S>()
+        }
+    }
+
+    pub fn default_input(&self) -> String {
+        format!("inputs/day{}.txt", self.day)
+    }
+
+    pub fn part(&self, part: u8) -> Option<PartFn> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+}
+
+// Every day registers its solver here
+pub const SOLVERS: [Solver; 15] = [
+    day1::SOLVER,
+    day2::SOLVER,
+    day3::SOLVER,
+    day4::SOLVER,
+    day5::SOLVER,
+    day6::SOLVER,
+    day7::SOLVER,
+    day8::SOLVER,
+    day9::SOLVER,
+    day10::SOLVER,
+    day11::SOLVER,
+    day12::SOLVER,
+    day13::SOLVER,
+    day14::SOLVER,
+    day15::SOLVER,
+];
+
+pub fn solver(day: u8) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|solver| solver.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_registry() {
+        assert!(SOLVERS
+            .iter()
+            .enumerate()
+            .all(|(i, s)| s.day as usize == i + 1));
+        assert_eq!(solver(7).unwrap().default_input(), "inputs/day7.txt");
+        assert!(solver(12).unwrap().part(3).is_none());
+        assert!(solver(16).is_none());
+        assert!(solver(1).unwrap().report.is_some());
+        assert_eq!(solver(1).unwrap().formats, &["table", "json"]);
+        assert!(solver(9).unwrap().report.is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let contents = fs::read_to_string("samples/day9.txt").unwrap();
+
+        assert_eq!((solver(9).unwrap().part1)(&contents), Ok("114".to_string()));
+        assert_eq!((solver(9).unwrap().part2)(&contents), Ok("2".to_string()));
+    }
+}
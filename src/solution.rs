@@ -0,0 +1,32 @@
+use crate::error::ParseError;
+use std::fmt::Display;
+
+// Shape shared by every day: parse the input once, then answer both parts from it
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> impl Display;
+
+    fn part2(input: &Self::Input) -> impl Display;
+
+    // Answers as text, for days where a part can still fail on input that parses
+    fn try_part1(input: &Self::Input) -> Result<String, ParseError> {
+        Ok(Self::part1(input).to_string())
+    }
+
+    fn try_part2(input: &Self::Input) -> Result<String, ParseError> {
+        Ok(Self::part2(input).to_string())
+    }
+}
+
+// Detailed breakdown of how a day arrives at its answers, in one of several output formats
+pub trait Report: Solution {
+    // Supported formats, the first one being the default
+    const FORMATS: &'static [&'static str];
+
+    fn report(input: &Self::Input, part: u8, format: &str) -> Result<String, ParseError>;
+}